@@ -0,0 +1,119 @@
+use super::*;
+use crate::proof_aggregation::*;
+use plonky2::plonk::proof::ProofWithPublicInputs;
+
+pub struct AggregateAction {
+    pub vk_paths: Vec<String>,
+    pub proof_paths: Vec<String>,
+    pub resulting_proof_file_path: String,
+    pub min_security_bits: Option<usize>,
+}
+
+impl AggregateAction {
+    pub fn run(&self) {
+        let verifier_data: Vec<VerifierCircuitData<F, AggregationConfig, D>> = self
+            .vk_paths
+            .iter()
+            .map(|vk_path| read_aggregation_verifying_key(vk_path, self.min_security_bits))
+            .collect();
+        let leaf_verifier_data = self._leaf_verifier_data(&verifier_data);
+        let compressed_proofs = self
+            .proof_paths
+            .iter()
+            .zip(leaf_verifier_data.iter())
+            .map(|(proof_path, verifier_data)| {
+                deserialize_proof_within_file_path(proof_path, verifier_data)
+            })
+            .collect();
+        let aggregated_proof = self.aggregate(&leaf_verifier_data, compressed_proofs);
+        write_bytes_to_file_path(aggregated_proof, &self.resulting_proof_file_path);
+    }
+
+    // Proofs of a single program share its verifying key, otherwise each proof comes with its own
+    fn _leaf_verifier_data<'a>(
+        &self,
+        verifier_data: &'a Vec<VerifierCircuitData<F, AggregationConfig, D>>,
+    ) -> Vec<&'a VerifierCircuitData<F, AggregationConfig, D>> {
+        match verifier_data.len() {
+            1 => self.proof_paths.iter().map(|_| &verifier_data[0]).collect(),
+            amount if amount == self.proof_paths.len() => verifier_data.iter().collect(),
+            _ => panic!(
+                "Must specify either a single verifying key for all the proofs or one for each proof"
+            ),
+        }
+    }
+
+    pub fn aggregate(
+        &self,
+        leaf_verifier_data: &Vec<&VerifierCircuitData<F, AggregationConfig, D>>,
+        compressed_proofs: Vec<CompressedProofWithPublicInputs<F, AggregationConfig, D>>,
+    ) -> Vec<u8> {
+        let proofs: Vec<ProofWithPublicInputs<F, AggregationConfig, D>> = compressed_proofs
+            .into_iter()
+            .zip(leaf_verifier_data.iter())
+            .enumerate()
+            .map(|(index, (compressed_proof, verifier_data))| {
+                self._checked_proof(verifier_data, index, compressed_proof)
+            })
+            .collect();
+        let leaves: Vec<AggregatedProofLeaf> = proofs
+            .iter()
+            .zip(leaf_verifier_data.iter())
+            .map(|(proof, verifier_data)| AggregatedProofLeaf {
+                circuit_digest: verifier_data.verifier_only.circuit_digest,
+                public_inputs: proof.public_inputs.clone(),
+            })
+            .collect();
+
+        let tree = build_aggregation_tree(leaf_verifier_data);
+        let aggregated_proof = tree.prove(proofs);
+        let root_verifier_data = tree.root_verifier_data();
+        let circuit_digest = &root_verifier_data.verifier_only.circuit_digest;
+        let compressed_aggregated_proof = aggregated_proof
+            .compress(circuit_digest, &root_verifier_data.common)
+            .unwrap();
+        serialize_aggregated_proof(&leaves, &compressed_aggregated_proof, circuit_digest)
+    }
+
+    // Invalid proofs are reported before building the aggregation tree
+    fn _checked_proof(
+        &self,
+        verifier_data: &VerifierCircuitData<F, AggregationConfig, D>,
+        index: usize,
+        compressed_proof: CompressedProofWithPublicInputs<F, AggregationConfig, D>,
+    ) -> ProofWithPublicInputs<F, AggregationConfig, D> {
+        let proof = compressed_proof
+            .decompress(
+                &verifier_data.verifier_only.circuit_digest,
+                &verifier_data.common,
+            )
+            .unwrap_or_else(|_| panic!("Proof {} is corrupted", index));
+        if verifier_data.verify(proof.clone()).is_err() {
+            panic!(
+                "Proof {} does not verify, so it cannot be aggregated",
+                index
+            );
+        }
+        proof
+    }
+}
+
+// Keccak proofs are too expensive to verify inside a plonky2 circuit, and a single leaf below the
+// minimum security would make the whole aggregated proof that weak
+pub fn read_aggregation_verifying_key(
+    vk_path: &String,
+    min_security_bits: Option<usize>,
+) -> VerifierCircuitData<F, AggregationConfig, D> {
+    if hash_config_within_file_path(vk_path, FileKind::VerifyingKey) != HashConfig::Poseidon {
+        panic!("Only proofs generated with --hash poseidon can be aggregated");
+    }
+    let (verifier_data, security_profile) =
+        deserialize_verifying_key_with_security_profile(&read_file_to_bytes(vk_path));
+    check_minimum_security(
+        &format!("The verifying key {}", vk_path),
+        security_profile,
+        &verifier_data.common.config,
+        min_security_bits,
+    );
+    verifier_data
+}
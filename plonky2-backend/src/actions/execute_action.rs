@@ -0,0 +1,22 @@
+use super::*;
+use crate::noir_abi::*;
+use crate::witness_solver::solve_witness_stack;
+
+pub struct ExecuteAction {
+    pub acir_program_json_path: String,
+    pub input_values_path: String,
+    pub resulting_witness_stack_path: String,
+}
+
+impl ExecuteAction {
+    pub fn run(&self) {
+        let acir_program: Program =
+            deserialize_program_within_file_path(&self.acir_program_json_path);
+        let abi = deserialize_abi_within_file_path(&self.acir_program_json_path);
+        let input_values = read_input_values_within_file_path(&self.input_values_path);
+
+        let initial_witness = initial_witness_from_input_values(&abi, &input_values);
+        let witness_stack = solve_witness_stack(&acir_program, initial_witness);
+        write_witnesses_to_file_path(witness_stack, self.resulting_witness_stack_path.clone());
+    }
+}
@@ -1,5 +1,14 @@
 use super::*;
 
+#[cfg(test)]
+mod tests;
+
+pub mod aggregate_action;
+pub mod execute_action;
+pub mod proof_as_fields_action;
 pub mod prove_action;
 pub mod verify_action;
+pub mod verify_aggregate_action;
+pub mod vk_as_fields_action;
+pub mod write_pk_action;
 pub mod write_vk_action;
@@ -0,0 +1,36 @@
+use super::*;
+use crate::circuit_translation::recursive_aggregation_translator::proof_fields;
+use plonky2::plonk::config::PoseidonGoldilocksConfig;
+
+pub struct ProofAsFieldsAction {
+    pub proof_path: String,
+    pub vk_path: String,
+    pub resulting_fields_path: String,
+}
+
+impl ProofAsFieldsAction {
+    // Only Poseidon proofs can be verified in-circuit, so the verifying key must use Poseidon
+    pub fn run(&self) {
+        let verifier_data =
+            deserialize_verifying_key_within_file_path::<PoseidonGoldilocksConfig>(&self.vk_path);
+        let compressed_proof = deserialize_proof_within_file_path(&self.proof_path, &verifier_data);
+        let fields = self.proof_as_fields(&verifier_data, compressed_proof);
+        write_fields_as_json_to_file_path(&fields, &self.resulting_fields_path);
+    }
+
+    // Proofs are written compressed, while the circuit verifies the full proof. The public
+    // inputs are not part of these fields, they are given separately to std::verify_proof
+    pub fn proof_as_fields(
+        &self,
+        verifier_data: &VerifierCircuitData<F, PoseidonGoldilocksConfig, D>,
+        compressed_proof: CompressedProofWithPublicInputs<F, PoseidonGoldilocksConfig, D>,
+    ) -> Vec<F> {
+        let proof = compressed_proof
+            .decompress(
+                &verifier_data.verifier_only.circuit_digest,
+                &verifier_data.common,
+            )
+            .expect("The proof is corrupted");
+        proof_fields(&proof.proof)
+    }
+}
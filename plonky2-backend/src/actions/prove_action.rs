@@ -1,94 +1,238 @@
-use std::collections::HashMap;
-
-use num_bigint::BigUint;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 
 use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2::field::types::Field;
 use plonky2::iop::target::Target;
 use plonky2::iop::witness::{PartialWitness, WitnessWrite};
 use plonky2::plonk::circuit_data::CircuitData;
-use plonky2::plonk::config::{GenericConfig, KeccakGoldilocksConfig};
+use plonky2::plonk::config::{KeccakGoldilocksConfig, PoseidonGoldilocksConfig};
 use plonky2::plonk::proof::ProofWithPublicInputs;
 
 use crate::circuit_translation;
+use crate::circuit_translation::constant_analysis::field_element_to_goldilocks_field;
+use crate::circuit_translation::emulated_bn254_translator::{
+    bn254_limbs_of, check_emulated_bn254_support, CircuitBuilderFromAcirToEmulatedBn254,
+};
+use crate::circuit_translation::targets::Bn254Target;
 use crate::circuit_translation::*;
+use crate::noir_abi::*;
 use crate::noir_and_plonky2_serialization::*;
+use crate::plonky2_config::*;
+use crate::witness_checker::check_witness_stack;
+use crate::witness_solver::solve_witness_stack;
 
-const D: usize = 2;
-type C = KeccakGoldilocksConfig;
-type F = <C as GenericConfig<D>>::F;
+type F = GoldilocksField;
 
 pub struct ProveAction {
     pub acir_program_json_path: String,
-    pub witness_stack_zip_path: String,
+    pub witness_stack_zip_path: Option<String>,
+    pub input_values_path: Option<String>,
+    pub proving_key_path: Option<String>,
     pub resulting_proof_file_path: String,
+    pub emulate_bn254: bool,
+    pub hash_config: HashConfig,
+    pub zero_knowledge: bool,
+    pub security_profile: SecurityProfile,
+    pub recursive_vk_paths: Vec<String>,
 }
 
 impl ProveAction {
     pub fn run(&self) {
+        match self.hash_config {
+            HashConfig::Keccak => self._run::<KeccakGoldilocksConfig>(),
+            HashConfig::Poseidon => self._run::<PoseidonGoldilocksConfig>(),
+        }
+    }
+
+    fn _run<C: BackendConfig>(&self) {
         let acir_program: Program =
             deserialize_program_within_file_path(&self.acir_program_json_path);
-        let witness_stack: WitnessStack =
-            deserialize_witnesses_within_file_path(self.witness_stack_zip_path.clone());
+        if self.emulate_bn254 {
+            if self.proving_key_path.is_some() {
+                panic!("Proving keys are not supported yet in emulated BN254 mode");
+            }
+            check_emulated_bn254_support(&acir_program);
+        }
+        let witness_stack = self._witness_stack_for(&acir_program);
 
-        let circuit = &acir_program.functions[0];
-        let (circuit_data, witness_target_map) =
-            self.generate_plonky2_circuit_from_acir_circuit(circuit);
-        let proof = self.generate_serialized_plonky2_proof(
-            witness_stack,
-            &witness_target_map,
-            &circuit_data,
-        );
+        let proof = match self.emulate_bn254 {
+            true => self._generate_serialized_emulated_bn254_proof::<C>(
+                &acir_program.functions[0],
+                witness_stack,
+            ),
+            false => {
+                check_witness_stack(&acir_program, &witness_stack);
+                let (circuit_data, witness_target_map, inlined_calls, computed_targets) =
+                    self._plonky2_circuit_for::<C>(&acir_program);
+                self.generate_serialized_plonky2_proof(
+                    witness_stack,
+                    &witness_target_map,
+                    &inlined_calls,
+                    &computed_targets,
+                    &circuit_data,
+                )
+            }
+        };
 
         self._write_proof_into_file(proof, &self.resulting_proof_file_path);
     }
 
+    // The witness is either read from a nargo witness file or solved from the program inputs
+    fn _witness_stack_for(&self, acir_program: &Program) -> WitnessStack {
+        match (&self.witness_stack_zip_path, &self.input_values_path) {
+            (Some(witness_stack_zip_path), None) => {
+                deserialize_witnesses_within_file_path(witness_stack_zip_path.clone())
+            }
+            (None, Some(input_values_path)) => {
+                let abi = deserialize_abi_within_file_path(&self.acir_program_json_path);
+                let input_values = read_input_values_within_file_path(input_values_path);
+                let initial_witness = initial_witness_from_input_values(&abi, &input_values);
+                solve_witness_stack(acir_program, initial_witness)
+            }
+            _ => panic!("Must specify either a witness file or the program inputs"),
+        }
+    }
+
     fn _write_proof_into_file(&self, proof: Vec<u8>, proof_path: &String) {
         write_bytes_to_file_path(proof, proof_path)
     }
 
-    pub fn generate_plonky2_circuit_from_acir_circuit(
+    // A proving key written by write_pk saves building the circuit again
+    fn _plonky2_circuit_for<C: BackendConfig>(
+        &self,
+        acir_program: &Program,
+    ) -> (
+        CircuitData<F, C, 2>,
+        HashMap<Witness, Target>,
+        Vec<InlinedCall>,
+        HashSet<Target>,
+    ) {
+        match &self.proving_key_path {
+            Some(proving_key_path) => deserialize_proving_key_within_file_path(
+                acir_program,
+                &ProvingKeySettings::new(
+                    self.zero_knowledge,
+                    self.security_profile,
+                    self.emulate_bn254,
+                    &self.recursive_vk_paths,
+                ),
+                proving_key_path,
+            ),
+            None => self._generate_plonky2_circuit(acir_program, self._abi_return_witnesses()),
+        }
+    }
+
+    fn _abi_return_witnesses(&self) -> Option<Vec<Witness>> {
+        abi_return_witnesses(&deserialize_abi_within_file_path(
+            &self.acir_program_json_path,
+        ))
+    }
+
+    pub fn generate_plonky2_circuit_from_acir_program<C: BackendConfig>(
+        &self,
+        program: &Program,
+    ) -> (
+        CircuitData<F, C, 2>,
+        HashMap<Witness, Target>,
+        Vec<InlinedCall>,
+        HashSet<Target>,
+    ) {
+        self._generate_plonky2_circuit(program, None)
+    }
+
+    fn _generate_plonky2_circuit<C: BackendConfig>(
+        &self,
+        program: &Program,
+        return_witnesses: Option<Vec<Witness>>,
+    ) -> (
+        CircuitData<F, C, 2>,
+        HashMap<Witness, Target>,
+        Vec<InlinedCall>,
+        HashSet<Target>,
+    ) {
+        let mut translator = circuit_translation::CircuitBuilderFromAcirToPlonky2::new_with_config(
+            circuit_config(self.zero_knowledge, self.security_profile),
+        );
+        translator.recursive_common_data =
+            deserialize_recursive_common_data_within_file_paths(&self.recursive_vk_paths);
+        translator.return_witnesses = return_witnesses;
+        translator.translate_program(program);
+        translator.unpack_program()
+    }
+
+    pub fn generate_emulated_bn254_circuit_from_acir_circuit<C: BackendConfig>(
         &self,
         circuit: &Circuit,
-    ) -> (CircuitData<F, C, 2>, HashMap<Witness, Target>) {
-        let mut translator = circuit_translation::CircuitBuilderFromAcirToPlonky2::new();
+    ) -> (CircuitData<F, C, 2>, HashMap<Witness, Bn254Target>) {
+        let mut translator = CircuitBuilderFromAcirToEmulatedBn254::new_with_config(
+            circuit_config(self.zero_knowledge, self.security_profile),
+        );
+        translator.return_witnesses = self._abi_return_witnesses();
         translator.translate_circuit(circuit);
         translator.unpack()
     }
 
-    fn _field_element_to_goldilocks_field(&self, fe: &FieldElement) -> F {
-        let fe_as_big_uint = BigUint::from_bytes_be(&fe.to_be_bytes() as &[u8]);
-        F::from_noncanonical_biguint(fe_as_big_uint)
+    fn _generate_serialized_emulated_bn254_proof<C: BackendConfig>(
+        &self,
+        circuit: &Circuit,
+        mut witness_stack: WitnessStack,
+    ) -> Vec<u8> {
+        let (circuit_data, witness_target_map) =
+            self.generate_emulated_bn254_circuit_from_acir_circuit::<C>(circuit);
+        let witnesses =
+            self._extract_emulated_bn254_witnesses(&mut witness_stack, &witness_target_map);
+        let proof = self.generate_plonky2_proof_from_partial_witnesses(&circuit_data, witnesses);
+        self._serialize_compressed_proof(proof, &circuit_data)
     }
 
-    fn generate_serialized_plonky2_proof(
+    fn generate_serialized_plonky2_proof<C: BackendConfig>(
         &self,
         mut witness_stack: WitnessStack,
         witness_target_map: &HashMap<Witness, Target>,
+        inlined_calls: &Vec<InlinedCall>,
+        computed_targets: &HashSet<Target>,
         circuit_data: &CircuitData<F, C, 2>,
     ) -> Vec<u8> {
         let proof = self.generate_plonky2_proof_from_witness_stack(
             &mut witness_stack,
             witness_target_map,
+            inlined_calls,
+            computed_targets,
             circuit_data,
         );
+        self._serialize_compressed_proof(proof, circuit_data)
+    }
+
+    fn _serialize_compressed_proof<C: BackendConfig>(
+        &self,
+        proof: ProofWithPublicInputs<GoldilocksField, C, 2>,
+        circuit_data: &CircuitData<F, C, 2>,
+    ) -> Vec<u8> {
         let verifier_data_digest = &circuit_data.verifier_only.circuit_digest;
         let common = &circuit_data.common;
         let compressed_proof = proof.compress(verifier_data_digest, common).unwrap();
-        compressed_proof.to_bytes()
+        serialize_compressed_proof(&compressed_proof, verifier_data_digest)
     }
 
-    pub fn generate_plonky2_proof_from_witness_stack(
+    pub fn generate_plonky2_proof_from_witness_stack<C: BackendConfig>(
         &self,
         mut witness_stack: &mut WitnessStack,
         witness_target_map: &HashMap<Witness, Target>,
+        inlined_calls: &Vec<InlinedCall>,
+        computed_targets: &HashSet<Target>,
         circuit_data: &CircuitData<GoldilocksField, C, 2>,
     ) -> ProofWithPublicInputs<GoldilocksField, C, 2> {
-        let witnesses = self._extract_witnesses(&mut witness_stack, witness_target_map);
+        let witnesses = self._extract_witnesses(
+            &mut witness_stack,
+            witness_target_map,
+            inlined_calls,
+            computed_targets,
+        );
         self.generate_plonky2_proof_from_partial_witnesses(circuit_data, witnesses)
     }
 
-    pub fn generate_plonky2_proof_from_partial_witnesses(
+    pub fn generate_plonky2_proof_from_partial_witnesses<C: BackendConfig>(
         &self,
         circuit_data: &CircuitData<GoldilocksField, C, 2>,
         witnesses: PartialWitness<GoldilocksField>,
@@ -96,20 +240,166 @@ impl ProveAction {
         circuit_data.prove(witnesses).unwrap()
     }
 
+    // The ACVM pushes a stack item when each enabled function call finishes, so main is on top
+    // and below it come its calls from the last one to the first, each followed by the calls
+    // nested in it. Disabled calls push nothing
     fn _extract_witnesses(
         &self,
         witness_stack: &mut WitnessStack,
         witness_target_map: &HashMap<Witness, Target>,
+        inlined_calls: &Vec<InlinedCall>,
+        computed_targets: &HashSet<Target>,
+    ) -> PartialWitness<GoldilocksField> {
+        let mut witnesses = PartialWitness::<F>::new();
+        let main_stack_item = witness_stack.pop().expect("Witness stack is empty");
+        self._set_nested_call_witness_values(
+            &mut witnesses,
+            witness_stack,
+            inlined_calls,
+            0..inlined_calls.len(),
+            &main_stack_item.witness,
+            computed_targets,
+        );
+        if !witness_stack.is_empty() {
+            panic!("The witness stack does not match the function calls of the program");
+        }
+        self._set_witness_values(
+            &mut witnesses,
+            main_stack_item.index,
+            main_stack_item.witness,
+            witness_target_map,
+            computed_targets,
+        );
+        witnesses
+    }
+
+    fn _set_nested_call_witness_values(
+        &self,
+        witnesses: &mut PartialWitness<GoldilocksField>,
+        witness_stack: &mut WitnessStack,
+        inlined_calls: &Vec<InlinedCall>,
+        nested_calls: Range<usize>,
+        caller_witness_map: &WitnessMap,
+        computed_targets: &HashSet<Target>,
+    ) {
+        let mut end_of_calls = nested_calls.end;
+        while end_of_calls > nested_calls.start {
+            let call_index = end_of_calls - 1;
+            let inlined_call = &inlined_calls[call_index];
+            let first_nested_call = call_index - inlined_call.amount_of_nested_calls;
+            if _call_is_enabled(&inlined_call.predicate, caller_witness_map) {
+                let stack_item = witness_stack
+                    .pop()
+                    .expect("The witness stack does not match the function calls of the program");
+                assert_eq!(
+                    inlined_call.function_id, stack_item.index,
+                    "The witness stack does not match the function calls of the program"
+                );
+                self._set_nested_call_witness_values(
+                    witnesses,
+                    witness_stack,
+                    inlined_calls,
+                    first_nested_call..call_index,
+                    &stack_item.witness,
+                    computed_targets,
+                );
+                self._set_witness_values(
+                    witnesses,
+                    stack_item.index,
+                    stack_item.witness,
+                    &inlined_call.witness_target_map,
+                    computed_targets,
+                );
+            } else {
+                for disabled_call in &inlined_calls[first_nested_call..=call_index] {
+                    self._set_disabled_call_witness_values(
+                        witnesses,
+                        disabled_call,
+                        computed_targets,
+                    );
+                }
+            }
+            end_of_calls = first_nested_call;
+        }
+    }
+
+    // The inputs of a disabled call are zero in the circuit, and so are its witnesses
+    fn _set_disabled_call_witness_values(
+        &self,
+        witnesses: &mut PartialWitness<GoldilocksField>,
+        inlined_call: &InlinedCall,
+        computed_targets: &HashSet<Target>,
+    ) {
+        for target in inlined_call.witness_target_map.values() {
+            if computed_targets.contains(target) {
+                continue;
+            }
+            witnesses.set_target(*target, F::ZERO);
+        }
+    }
+
+    // Witnesses that no translated opcode references (e.g. Brillig temporaries) are skipped
+    fn _set_witness_values(
+        &self,
+        witnesses: &mut PartialWitness<GoldilocksField>,
+        function_id: u32,
+        witness_map: WitnessMap,
+        witness_target_map: &HashMap<Witness, Target>,
+        computed_targets: &HashSet<Target>,
+    ) {
+        let mut witnesses_without_value: Vec<u32> = witness_target_map
+            .iter()
+            .filter(|(witness, target)| {
+                witness_map.get(witness).is_none() && !computed_targets.contains(target)
+            })
+            .map(|(witness, _)| witness.0)
+            .collect();
+        witnesses_without_value.sort();
+        if !witnesses_without_value.is_empty() {
+            panic!(
+                "Witnesses {:?} of function {} are used by the circuit but have no value",
+                witnesses_without_value, function_id
+            );
+        }
+
+        for (witness, value) in witness_map.into_iter() {
+            if let Some(plonky2_target) = witness_target_map.get(&witness) {
+                witnesses.set_target(*plonky2_target, field_element_to_goldilocks_field(&value));
+            }
+        }
+    }
+
+    fn _extract_emulated_bn254_witnesses(
+        &self,
+        witness_stack: &mut WitnessStack,
+        witness_target_map: &HashMap<Witness, Bn254Target>,
     ) -> PartialWitness<GoldilocksField> {
         let mut witnesses = PartialWitness::<F>::new();
         let witness_map = witness_stack.pop().unwrap().witness;
         for (witness, value) in witness_map.into_iter() {
-            let plonky2_target = witness_target_map.get(&witness).unwrap();
-            witnesses.set_target(
-                *plonky2_target,
-                self._field_element_to_goldilocks_field(&value),
-            );
+            if let Some(bn254_target) = witness_target_map.get(&witness) {
+                witnesses.set_target_arr(&bn254_target.limbs, &bn254_limbs_of(&value));
+            }
         }
         witnesses
     }
 }
+
+fn _call_is_enabled(predicate: &Option<Expression>, caller_witness_map: &WitnessMap) -> bool {
+    let Some(predicate) = predicate else {
+        return true;
+    };
+    let value_of = |witness: &Witness| {
+        *caller_witness_map
+            .get(witness)
+            .expect("The predicate of a function call has no value")
+    };
+    let mut predicate_value = predicate.q_c;
+    for (coefficient, lhs, rhs) in &predicate.mul_terms {
+        predicate_value = predicate_value + *coefficient * value_of(lhs) * value_of(rhs);
+    }
+    for (coefficient, witness) in &predicate.linear_combinations {
+        predicate_value = predicate_value + *coefficient * value_of(witness);
+    }
+    !predicate_value.is_zero()
+}
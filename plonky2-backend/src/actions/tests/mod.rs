@@ -0,0 +1,16 @@
+use super::*;
+use plonky2::plonk::config::KeccakGoldilocksConfig;
+
+type C = KeccakGoldilocksConfig;
+
+#[cfg(test)]
+mod test_aggregate_action;
+
+#[cfg(test)]
+mod test_as_fields_actions;
+
+#[cfg(test)]
+mod test_prove_action;
+
+#[cfg(test)]
+mod test_verify_action;
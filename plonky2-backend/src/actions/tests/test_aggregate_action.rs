@@ -0,0 +1,268 @@
+use super::*;
+use crate::actions::aggregate_action::{read_aggregation_verifying_key, AggregateAction};
+use crate::actions::verify_aggregate_action::VerifyAggregateAction;
+use crate::circuit_translation::tests::factories::plonky2_factory::*;
+use crate::proof_aggregation::*;
+use plonky2::field::types::Field;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CircuitConfig;
+
+fn _aggregate_action() -> AggregateAction {
+    AggregateAction {
+        vk_paths: vec![],
+        proof_paths: vec![],
+        resulting_proof_file_path: String::new(),
+        min_security_bits: None,
+    }
+}
+
+fn _verify_aggregate_action() -> VerifyAggregateAction {
+    VerifyAggregateAction {
+        vk_paths: vec![],
+        aggregated_proof_path: String::new(),
+        min_security_bits: None,
+    }
+}
+
+fn _public_inputs_of(included_programs: &Vec<(usize, AggregatedProofLeaf)>) -> Vec<Vec<F>> {
+    included_programs
+        .iter()
+        .map(|(_, leaf)| leaf.public_inputs.clone())
+        .collect()
+}
+
+#[test]
+fn test_an_odd_amount_of_proofs_of_a_program_is_aggregated_into_one() {
+    // Given
+    let square = power_circuit::<AggregationConfig>(2);
+    let verifier_data = square.0.verifier_data();
+    let proofs = vec![
+        compressed_power_proof(&square, 2),
+        compressed_power_proof(&square, 3),
+        compressed_power_proof(&square, 4),
+    ];
+
+    // When
+    let aggregated_proof = _aggregate_action().aggregate(
+        &vec![&verifier_data, &verifier_data, &verifier_data],
+        proofs,
+    );
+
+    //Then
+    let included_programs =
+        _verify_aggregate_action().verify(&vec![verifier_data], &aggregated_proof);
+    assert_eq!(
+        vec![0, 0, 0],
+        included_programs
+            .iter()
+            .map(|(program, _)| *program)
+            .collect::<Vec<usize>>()
+    );
+    assert_eq!(
+        vec![
+            vec![F::from_canonical_u64(4)],
+            vec![F::from_canonical_u64(9)],
+            vec![F::from_canonical_u64(16)],
+        ],
+        _public_inputs_of(&included_programs)
+    );
+}
+
+#[test]
+fn test_proofs_of_different_programs_are_aggregated_into_one() {
+    // Given
+    let square = power_circuit::<AggregationConfig>(2);
+    let cube = power_circuit::<AggregationConfig>(3);
+    let square_verifier_data = square.0.verifier_data();
+    let cube_verifier_data = cube.0.verifier_data();
+    let proofs = vec![
+        compressed_power_proof(&square, 2),
+        compressed_power_proof(&cube, 2),
+        compressed_power_proof(&square, 3),
+    ];
+
+    // When
+    let aggregated_proof = _aggregate_action().aggregate(
+        &vec![
+            &square_verifier_data,
+            &cube_verifier_data,
+            &square_verifier_data,
+        ],
+        proofs,
+    );
+
+    //Then
+    let included_programs = _verify_aggregate_action().verify(
+        &vec![square_verifier_data, cube_verifier_data],
+        &aggregated_proof,
+    );
+    assert_eq!(
+        vec![0, 1, 0],
+        included_programs
+            .iter()
+            .map(|(program, _)| *program)
+            .collect::<Vec<usize>>()
+    );
+    assert_eq!(
+        vec![
+            vec![F::from_canonical_u64(4)],
+            vec![F::from_canonical_u64(8)],
+            vec![F::from_canonical_u64(9)],
+        ],
+        _public_inputs_of(&included_programs)
+    );
+}
+
+#[test]
+fn test_the_commitment_depends_on_the_program_and_order_of_each_leaf() {
+    // Given
+    let square_digest = power_circuit::<AggregationConfig>(2)
+        .0
+        .verifier_only
+        .circuit_digest;
+    let cube_digest = power_circuit::<AggregationConfig>(3)
+        .0
+        .verifier_only
+        .circuit_digest;
+    let leaf = |circuit_digest, value| AggregatedProofLeaf {
+        circuit_digest,
+        public_inputs: vec![F::from_canonical_u64(value)],
+    };
+
+    // When
+    let commitment = leaves_commitment(&vec![leaf(square_digest, 4), leaf(cube_digest, 8)]);
+
+    //Then
+    assert_ne!(
+        leaves_commitment(&vec![leaf(cube_digest, 8), leaf(square_digest, 4)]),
+        commitment
+    );
+    assert_ne!(
+        leaves_commitment(&vec![leaf(square_digest, 4), leaf(square_digest, 8)]),
+        commitment
+    );
+}
+
+#[test]
+#[should_panic(expected = "The aggregated proof does not commit to the leaves it lists")]
+fn test_verify_aggregate_rejects_tampered_public_inputs() {
+    // Given
+    let square = power_circuit::<AggregationConfig>(2);
+    let verifier_data = square.0.verifier_data();
+    let proofs = vec![
+        compressed_power_proof(&square, 2),
+        compressed_power_proof(&square, 3),
+    ];
+    let aggregated_proof =
+        _aggregate_action().aggregate(&vec![&verifier_data, &verifier_data], proofs);
+    let tree = build_aggregation_tree(&vec![&verifier_data, &verifier_data]);
+    let root_verifier_data = tree.root_verifier_data();
+    let compressed_proof = deserialize_aggregated_proof(&aggregated_proof, &root_verifier_data);
+    let mut leaves = deserialize_aggregated_proof_leaves(&aggregated_proof);
+    leaves[1].public_inputs = vec![F::from_canonical_u64(10)];
+    let tampered_aggregated_proof = serialize_aggregated_proof(
+        &leaves,
+        &compressed_proof,
+        &root_verifier_data.verifier_only.circuit_digest,
+    );
+
+    // When
+    _verify_aggregate_action().verify(&vec![verifier_data], &tampered_aggregated_proof);
+}
+
+#[test]
+#[should_panic(
+    expected = "Proof 1 of the aggregated proof is not a proof of any of the given programs"
+)]
+fn test_verify_aggregate_rejects_proofs_of_programs_that_were_not_given() {
+    // Given
+    let square = power_circuit::<AggregationConfig>(2);
+    let cube = power_circuit::<AggregationConfig>(3);
+    let square_verifier_data = square.0.verifier_data();
+    let cube_verifier_data = cube.0.verifier_data();
+    let proofs = vec![
+        compressed_power_proof(&square, 2),
+        compressed_power_proof(&cube, 2),
+    ];
+    let aggregated_proof =
+        _aggregate_action().aggregate(&vec![&square_verifier_data, &cube_verifier_data], proofs);
+
+    // When
+    _verify_aggregate_action().verify(&vec![square_verifier_data], &aggregated_proof);
+}
+
+#[test]
+#[should_panic(expected = "Only proofs generated with --hash poseidon can be aggregated")]
+fn test_aggregate_rejects_keccak_verifying_keys() {
+    // Given
+    let mut builder = CircuitBuilder::<F, D>::new(CircuitConfig::standard_recursion_config());
+    let x = builder.add_virtual_target();
+    builder.register_public_input(x);
+    let circuit_data = builder.build::<C>();
+    let vk_path = std::env::temp_dir()
+        .join("test_aggregate_action_keccak_vk")
+        .to_string_lossy()
+        .to_string();
+    write_bytes_to_file_path(
+        serialize_verifying_key(&circuit_data.verifier_data()),
+        &vk_path,
+    );
+    let mut aggregate_action = _aggregate_action();
+    aggregate_action.vk_paths = vec![vk_path];
+
+    // When
+    aggregate_action.run();
+}
+
+fn _fast_dev_verifying_key_path(name: &str) -> String {
+    let config = circuit_config(false, SecurityProfile::FastDev);
+    let square = power_circuit_with_config::<AggregationConfig>(2, config);
+    let vk_path = std::env::temp_dir()
+        .join(name)
+        .to_string_lossy()
+        .to_string();
+    write_bytes_to_file_path(serialize_verifying_key(&square.0.verifier_data()), &vk_path);
+    vk_path
+}
+
+#[test]
+#[should_panic(
+    expected = "was built with the fast-dev profile, which provides 30 bits of conjectured security, but at least 100 are required"
+)]
+fn test_aggregate_rejects_fast_dev_leaves() {
+    // Given
+    let mut aggregate_action = _aggregate_action();
+    aggregate_action.vk_paths = vec![_fast_dev_verifying_key_path(
+        "test_aggregate_action_fast_dev_vk",
+    )];
+
+    // When
+    aggregate_action.run();
+}
+
+#[test]
+#[should_panic(
+    expected = "was built with the fast-dev profile, which provides 30 bits of conjectured security, but at least 100 are required"
+)]
+fn test_verify_aggregate_rejects_fast_dev_leaves() {
+    // Given
+    let mut verify_aggregate_action = _verify_aggregate_action();
+    verify_aggregate_action.vk_paths = vec![_fast_dev_verifying_key_path(
+        "test_verify_aggregate_action_fast_dev_vk",
+    )];
+
+    // When
+    verify_aggregate_action.run();
+}
+
+#[test]
+fn test_aggregate_accepts_fast_dev_leaves_when_the_minimum_security_is_lowered() {
+    // Given
+    let vk_path = _fast_dev_verifying_key_path("test_aggregate_action_lowered_floor_vk");
+
+    // When
+    let verifier_data = read_aggregation_verifying_key(&vk_path, Some(30));
+
+    //Then
+    assert_eq!(30, conjectured_security_bits(&verifier_data.common.config));
+}
@@ -0,0 +1,102 @@
+use super::*;
+use crate::actions::proof_as_fields_action::ProofAsFieldsAction;
+use crate::actions::vk_as_fields_action::VkAsFieldsAction;
+use crate::circuit_translation::recursive_aggregation_translator::*;
+use crate::circuit_translation::tests::factories::plonky2_factory::*;
+use plonky2::field::types::Field;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2::plonk::config::PoseidonGoldilocksConfig;
+
+fn _proof_as_fields_action() -> ProofAsFieldsAction {
+    ProofAsFieldsAction {
+        proof_path: String::new(),
+        vk_path: String::new(),
+        resulting_fields_path: String::new(),
+    }
+}
+
+fn _vk_as_fields_action() -> VkAsFieldsAction {
+    VkAsFieldsAction {
+        vk_path: String::new(),
+        resulting_fields_path: String::new(),
+    }
+}
+
+#[test]
+fn test_proof_as_fields_decompresses_the_proof_written_by_prove() {
+    // Given
+    let square = power_circuit::<PoseidonGoldilocksConfig>(2);
+    let proof = power_proof(&square, 3);
+    let circuit_data = &square.0;
+    let circuit_digest = circuit_data.verifier_only.circuit_digest;
+    let compressed_proof = proof
+        .clone()
+        .compress(&circuit_digest, &circuit_data.common)
+        .unwrap();
+    let serialized_proof = serialize_compressed_proof(&compressed_proof, &circuit_digest);
+    let verifier_data = circuit_data.verifier_data();
+
+    // When
+    let fields = _proof_as_fields_action().proof_as_fields(
+        &verifier_data,
+        deserialize_proof(&serialized_proof, &verifier_data),
+    );
+
+    //Then
+    assert_eq!(proof_fields(&proof.proof), fields);
+}
+
+#[test]
+fn test_proof_as_fields_follows_the_layout_of_the_recursive_aggregation_translator() {
+    // Given
+    let square = power_circuit::<PoseidonGoldilocksConfig>(2);
+    let proof = power_proof(&square, 3);
+    let circuit_data = &square.0;
+    let mut builder = CircuitBuilder::<F, D>::new(CircuitConfig::standard_recursion_config());
+    let proof_target = builder.add_virtual_proof_with_pis(&circuit_data.common);
+
+    // When
+    let compressed_proof = proof
+        .compress(
+            &circuit_data.verifier_only.circuit_digest,
+            &circuit_data.common,
+        )
+        .unwrap();
+    let fields =
+        _proof_as_fields_action().proof_as_fields(&circuit_data.verifier_data(), compressed_proof);
+
+    //Then
+    assert_eq!(proof_targets(&proof_target.proof).len(), fields.len());
+}
+
+#[test]
+fn test_vk_as_fields_starts_with_the_key_hash() {
+    // Given
+    let (circuit_data, _) = power_circuit::<PoseidonGoldilocksConfig>(2);
+
+    // When
+    let fields = _vk_as_fields_action().vk_as_fields(&circuit_data.verifier_data());
+
+    //Then
+    let verification_key = verifier_data_fields(&circuit_data.verifier_only);
+    assert_eq!(69, fields.len());
+    assert_eq!(key_hash(&verification_key), fields[0]);
+    assert_eq!(verification_key, fields[1..].to_vec());
+    assert_eq!(
+        circuit_data.verifier_only.circuit_digest.elements.to_vec(),
+        fields[1..5].to_vec()
+    );
+}
+
+#[test]
+fn test_fields_are_written_as_a_json_array_of_hex_strings() {
+    // Given
+    let fields = vec![F::ONE, F::NEG_ONE];
+
+    // When
+    let json = fields_as_json(&fields);
+
+    //Then
+    assert_eq!(r#"["0x0000000000000001","0xffffffff00000000"]"#, json);
+}
@@ -0,0 +1,578 @@
+use super::*;
+use crate::actions::prove_action::ProveAction;
+use crate::circuit_translation::tests::factories::circuit_factory::circuit_with_opcodes;
+use crate::circuit_translation::tests::factories::utils::witness_map_from_values;
+use crate::noir_abi::*;
+use crate::noir_and_plonky2_serialization::*;
+use crate::witness_solver::solve_witness_stack;
+use acir::brillig::{BinaryFieldOp, BitSize, MemoryAddress, Opcode as BrilligOpcode};
+use acir::circuit::brillig::{BrilligBytecode, BrilligInputs, BrilligOutputs};
+use plonky2::field::types::Field;
+use plonky2::plonk::config::PoseidonGoldilocksConfig;
+use serde_json::json;
+
+fn _prove_action() -> ProveAction {
+    ProveAction {
+        acir_program_json_path: String::new(),
+        witness_stack_zip_path: None,
+        input_values_path: None,
+        proving_key_path: None,
+        resulting_proof_file_path: String::new(),
+        emulate_bn254: false,
+        hash_config: HashConfig::Keccak,
+        zero_knowledge: false,
+        security_profile: SecurityProfile::Standard100Bit,
+        recursive_vk_paths: vec![],
+    }
+}
+
+// fn main(x: pub Field) {
+//     assert(double(x) == 6);
+// }
+// #[fold]
+// fn double(a: Field) -> Field { a + a }
+fn _double_program() -> Program {
+    let main = circuit_with_opcodes(
+        vec![
+            Opcode::Call {
+                id: 1,
+                inputs: vec![Witness(0)],
+                outputs: vec![Witness(1)],
+                predicate: None,
+            },
+            Opcode::AssertZero(Expression {
+                mul_terms: vec![],
+                linear_combinations: vec![(FieldElement::one(), Witness(1))],
+                q_c: -FieldElement::from(6u128),
+            }),
+        ],
+        vec![Witness(0)],
+        vec![],
+    );
+    let double = circuit_with_opcodes(
+        vec![Opcode::AssertZero(Expression {
+            mul_terms: vec![],
+            linear_combinations: vec![
+                (FieldElement::from(2u128), Witness(0)),
+                (-FieldElement::one(), Witness(1)),
+            ],
+            q_c: FieldElement::zero(),
+        })],
+        vec![],
+        vec![Witness(1)],
+    );
+    Program {
+        functions: vec![main, double],
+        unconstrained_functions: vec![],
+    }
+}
+
+#[test]
+fn test_prove_action_uses_every_item_of_the_witness_stack() {
+    // Given
+    let prove_action = _prove_action();
+    let program = _double_program();
+    let (circuit_data, witness_target_map, inlined_calls, computed_targets) =
+        prove_action.generate_plonky2_circuit_from_acir_program::<C>(&program);
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(1, witness_map_from_values(vec![(0, 3), (1, 6)]));
+    witness_stack.push(0, witness_map_from_values(vec![(0, 3), (1, 6)]));
+
+    // When
+    let proof = prove_action.generate_plonky2_proof_from_witness_stack(
+        &mut witness_stack,
+        &witness_target_map,
+        &inlined_calls,
+        &computed_targets,
+        &circuit_data,
+    );
+
+    //Then
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "The witness stack does not match the function calls of the program")]
+fn test_prove_action_fails_if_the_witness_stack_misses_a_function_call() {
+    // Given
+    let prove_action = _prove_action();
+    let program = _double_program();
+    let (circuit_data, witness_target_map, inlined_calls, computed_targets) =
+        prove_action.generate_plonky2_circuit_from_acir_program::<C>(&program);
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(0, witness_map_from_values(vec![(0, 3), (1, 6)]));
+
+    // When
+    prove_action.generate_plonky2_proof_from_witness_stack(
+        &mut witness_stack,
+        &witness_target_map,
+        &inlined_calls,
+        &computed_targets,
+        &circuit_data,
+    );
+}
+
+// fn main(x: pub Field, enabled: bool) {
+//     let doubled = if enabled { double(x) } else { 0 };
+//     assert(doubled == 6 * enabled);
+// }
+fn _conditional_double_program() -> Program {
+    let mut program = _double_program();
+    program.functions[0].opcodes = vec![
+        Opcode::Call {
+            id: 1,
+            inputs: vec![Witness(0)],
+            outputs: vec![Witness(1)],
+            predicate: Some(Expression::from(Witness(2))),
+        },
+        Opcode::AssertZero(Expression {
+            mul_terms: vec![],
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(1)),
+                (-FieldElement::from(6u128), Witness(2)),
+            ],
+            q_c: FieldElement::zero(),
+        }),
+    ];
+    program
+}
+
+#[test]
+fn test_prove_action_uses_the_stack_item_of_a_call_enabled_by_its_predicate() {
+    // Given
+    let prove_action = _prove_action();
+    let program = _conditional_double_program();
+    let (circuit_data, witness_target_map, inlined_calls, computed_targets) =
+        prove_action.generate_plonky2_circuit_from_acir_program::<C>(&program);
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(1, witness_map_from_values(vec![(0, 3), (1, 6)]));
+    witness_stack.push(0, witness_map_from_values(vec![(0, 3), (1, 6), (2, 1)]));
+
+    // When
+    let proof = prove_action.generate_plonky2_proof_from_witness_stack(
+        &mut witness_stack,
+        &witness_target_map,
+        &inlined_calls,
+        &computed_targets,
+        &circuit_data,
+    );
+
+    //Then
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_prove_action_proves_a_call_disabled_by_its_predicate_without_a_stack_item() {
+    // Given
+    let prove_action = _prove_action();
+    let program = _conditional_double_program();
+    let (circuit_data, witness_target_map, inlined_calls, computed_targets) =
+        prove_action.generate_plonky2_circuit_from_acir_program::<C>(&program);
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(0, witness_map_from_values(vec![(0, 3), (1, 0), (2, 0)]));
+
+    // When
+    let proof = prove_action.generate_plonky2_proof_from_witness_stack(
+        &mut witness_stack,
+        &witness_target_map,
+        &inlined_calls,
+        &computed_targets,
+        &circuit_data,
+    );
+
+    //Then
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_prove_action_skips_witnesses_that_are_not_used_by_the_circuit() {
+    // Given
+    let prove_action = _prove_action();
+    let program = _double_program();
+    let (circuit_data, witness_target_map, inlined_calls, computed_targets) =
+        prove_action.generate_plonky2_circuit_from_acir_program::<C>(&program);
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(1, witness_map_from_values(vec![(0, 3), (1, 6), (2, 42)]));
+    witness_stack.push(0, witness_map_from_values(vec![(0, 3), (1, 6), (2, 42)]));
+
+    // When
+    let proof = prove_action.generate_plonky2_proof_from_witness_stack(
+        &mut witness_stack,
+        &witness_target_map,
+        &inlined_calls,
+        &computed_targets,
+        &circuit_data,
+    );
+
+    //Then
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_prove_action_does_not_need_values_for_witnesses_computed_in_circuit() {
+    // Given
+    let prove_action = _prove_action();
+    let program = _double_program();
+    let (circuit_data, witness_target_map, inlined_calls, computed_targets) =
+        prove_action.generate_plonky2_circuit_from_acir_program::<C>(&program);
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(1, witness_map_from_values(vec![(0, 3)]));
+    witness_stack.push(0, witness_map_from_values(vec![(0, 3)]));
+
+    // When
+    let proof = prove_action.generate_plonky2_proof_from_witness_stack(
+        &mut witness_stack,
+        &witness_target_map,
+        &inlined_calls,
+        &computed_targets,
+        &circuit_data,
+    );
+
+    //Then
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Witnesses [0] of function 0 are used by the circuit but have no value")]
+fn test_prove_action_reports_the_witnesses_used_by_the_circuit_that_have_no_value() {
+    // Given
+    let prove_action = _prove_action();
+    let program = _double_program();
+    let (circuit_data, witness_target_map, inlined_calls, computed_targets) =
+        prove_action.generate_plonky2_circuit_from_acir_program::<C>(&program);
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(1, witness_map_from_values(vec![(0, 3)]));
+    witness_stack.push(0, witness_map_from_values(vec![(1, 6)]));
+
+    // When
+    prove_action.generate_plonky2_proof_from_witness_stack(
+        &mut witness_stack,
+        &witness_target_map,
+        &inlined_calls,
+        &computed_targets,
+        &circuit_data,
+    );
+}
+
+#[test]
+fn test_prove_action_can_solve_the_witness_from_the_program_inputs() {
+    // Given
+    let prove_action = _prove_action();
+    let program = _double_program();
+    let abi = json!({
+        "parameters": [{"name": "x", "type": {"kind": "field"}, "visibility": "public"}],
+        "return_type": null,
+        "error_types": {}
+    });
+    let initial_witness =
+        initial_witness_from_input_values(&abi, &parse_toml_input_values("x = 3"));
+
+    // When
+    let mut witness_stack = solve_witness_stack(&program, initial_witness);
+
+    //Then
+    let (circuit_data, witness_target_map, inlined_calls, computed_targets) =
+        prove_action.generate_plonky2_circuit_from_acir_program::<C>(&program);
+    let proof = prove_action.generate_plonky2_proof_from_witness_stack(
+        &mut witness_stack,
+        &witness_target_map,
+        &inlined_calls,
+        &computed_targets,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Failed to solve the witness")]
+fn test_witness_solving_fails_for_inputs_that_do_not_satisfy_the_program() {
+    // Given
+    let program = _double_program();
+    let initial_witness = witness_map_from_values(vec![(0, 4)]);
+
+    // When
+    solve_witness_stack(&program, initial_witness);
+}
+
+// fn main(x: Field) -> pub Field {
+//     1 / x
+// }
+fn _inverse_program() -> Program {
+    let main = circuit_with_opcodes(
+        vec![
+            Opcode::BrilligCall {
+                id: 0,
+                inputs: vec![BrilligInputs::Single(Expression::from(Witness(0)))],
+                outputs: vec![BrilligOutputs::Simple(Witness(1))],
+                predicate: None,
+            },
+            Opcode::AssertZero(Expression {
+                mul_terms: vec![(FieldElement::one(), Witness(0), Witness(1))],
+                linear_combinations: vec![],
+                q_c: -FieldElement::one(),
+            }),
+        ],
+        vec![],
+        vec![Witness(1)],
+    );
+    let inverse = BrilligBytecode {
+        bytecode: vec![
+            BrilligOpcode::CalldataCopy {
+                destination_address: MemoryAddress(0),
+                size: 1,
+                offset: 0,
+            },
+            BrilligOpcode::Const {
+                destination: MemoryAddress(1),
+                bit_size: BitSize::Field,
+                value: FieldElement::one(),
+            },
+            BrilligOpcode::BinaryFieldOp {
+                destination: MemoryAddress(0),
+                op: BinaryFieldOp::Div,
+                lhs: MemoryAddress(1),
+                rhs: MemoryAddress(0),
+            },
+            BrilligOpcode::Stop {
+                return_data_offset: 0,
+                return_data_size: 1,
+            },
+        ],
+    };
+    Program {
+        functions: vec![main],
+        unconstrained_functions: vec![inverse],
+    }
+}
+
+#[test]
+fn test_witness_solving_executes_unconstrained_functions() {
+    // Given
+    let program = _inverse_program();
+    let initial_witness = witness_map_from_values(vec![(0, 2)]);
+
+    // When
+    let mut witness_stack = solve_witness_stack(&program, initial_witness);
+
+    //Then
+    let main_witness = witness_stack.pop().unwrap().witness;
+    assert_eq!(
+        Some(&FieldElement::from(2u128).inverse()),
+        main_witness.get(&Witness(1))
+    );
+}
+
+fn _proving_key_settings() -> ProvingKeySettings {
+    ProvingKeySettings::new(false, SecurityProfile::Standard100Bit, false, &vec![])
+}
+
+// Writes a proving key of the double program, built by _prove_action, with the given settings
+fn _write_double_program_proving_key(name: &str, settings: &ProvingKeySettings) -> String {
+    let program = _double_program();
+    let (circuit_data, witness_target_map, inlined_calls, computed_targets) =
+        _prove_action().generate_plonky2_circuit_from_acir_program::<C>(&program);
+    let proving_key_path = std::env::temp_dir()
+        .join(name)
+        .to_string_lossy()
+        .to_string();
+    write_proving_key_to_file_path(
+        &program,
+        settings,
+        &circuit_data,
+        &witness_target_map,
+        &inlined_calls,
+        &computed_targets,
+        &proving_key_path,
+    );
+    proving_key_path
+}
+
+#[test]
+fn test_prove_action_can_prove_from_a_proving_key_written_to_a_file() {
+    // Given
+    let prove_action = _prove_action();
+    let program = _double_program();
+    let (circuit_data, _, _, _) =
+        prove_action.generate_plonky2_circuit_from_acir_program::<C>(&program);
+    let proving_key_path = _write_double_program_proving_key(
+        "test_prove_action_proving_key",
+        &_proving_key_settings(),
+    );
+
+    // When
+    let (
+        loaded_circuit_data,
+        loaded_witness_target_map,
+        loaded_inlined_calls,
+        loaded_computed_targets,
+    ) = deserialize_proving_key_within_file_path::<C>(
+        &program,
+        &_proving_key_settings(),
+        &proving_key_path,
+    );
+
+    //Then
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(1, witness_map_from_values(vec![(0, 3)]));
+    witness_stack.push(0, witness_map_from_values(vec![(0, 3)]));
+    let proof = prove_action.generate_plonky2_proof_from_witness_stack(
+        &mut witness_stack,
+        &loaded_witness_target_map,
+        &loaded_inlined_calls,
+        &loaded_computed_targets,
+        &loaded_circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "The proving key was written for a different program")]
+fn test_prove_action_rejects_a_proving_key_written_for_another_program() {
+    // Given
+    let proving_key_path = _write_double_program_proving_key(
+        "test_prove_action_proving_key_of_another_program",
+        &_proving_key_settings(),
+    );
+
+    // When
+    deserialize_proving_key_within_file_path::<C>(
+        &_sum_program(),
+        &_proving_key_settings(),
+        &proving_key_path,
+    );
+}
+
+#[test]
+#[should_panic(expected = "The proving key was written with --no-zk, but prove was run without it")]
+fn test_prove_action_rejects_a_proving_key_written_in_another_zero_knowledge_mode() {
+    // Given
+    let proving_key_path = _write_double_program_proving_key(
+        "test_prove_action_proving_key_without_zk",
+        &_proving_key_settings(),
+    );
+    let mut settings = _proving_key_settings();
+    settings.zero_knowledge = true;
+
+    // When
+    deserialize_proving_key_within_file_path::<C>(&_double_program(), &settings, &proving_key_path);
+}
+
+#[test]
+#[should_panic(
+    expected = "The proving key was written with --profile standard-100bit, but prove was run with --profile fast-dev"
+)]
+fn test_prove_action_rejects_a_proving_key_written_with_another_profile() {
+    // Given
+    let proving_key_path = _write_double_program_proving_key(
+        "test_prove_action_proving_key_of_another_profile",
+        &_proving_key_settings(),
+    );
+    let mut settings = _proving_key_settings();
+    settings.security_profile = SecurityProfile::FastDev;
+
+    // When
+    deserialize_proving_key_within_file_path::<C>(&_double_program(), &settings, &proving_key_path);
+}
+
+#[test]
+#[should_panic(
+    expected = "The proving key was written with other --recursive-vk verifying keys than the ones given to prove"
+)]
+fn test_prove_action_rejects_a_proving_key_written_with_other_recursive_verifying_keys() {
+    // Given
+    let proving_key_path = _write_double_program_proving_key(
+        "test_prove_action_proving_key_without_recursive_vks",
+        &_proving_key_settings(),
+    );
+    let recursive_vk_path = std::env::temp_dir()
+        .join("test_prove_action_recursive_vk")
+        .to_string_lossy()
+        .to_string();
+    write_bytes_to_file_path(vec![1, 2, 3], &recursive_vk_path);
+    let settings = ProvingKeySettings::new(
+        false,
+        SecurityProfile::Standard100Bit,
+        false,
+        &vec![recursive_vk_path],
+    );
+
+    // When
+    deserialize_proving_key_within_file_path::<C>(&_double_program(), &settings, &proving_key_path);
+}
+
+#[test]
+#[should_panic(
+    expected = "The proving key file uses the keccak hash config, but poseidon was expected"
+)]
+fn test_prove_action_rejects_a_proving_key_written_with_another_hash_config() {
+    // Given
+    let proving_key_path = _write_double_program_proving_key(
+        "test_prove_action_keccak_proving_key",
+        &_proving_key_settings(),
+    );
+
+    // When
+    deserialize_proving_key_within_file_path::<PoseidonGoldilocksConfig>(
+        &_double_program(),
+        &_proving_key_settings(),
+        &proving_key_path,
+    );
+}
+
+// fn main(x: Field, y: Field) -> pub Field {
+//     x + y
+// }
+fn _sum_program() -> Program {
+    let main = circuit_with_opcodes(
+        vec![Opcode::AssertZero(Expression {
+            mul_terms: vec![],
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(0)),
+                (FieldElement::one(), Witness(1)),
+                (-FieldElement::one(), Witness(2)),
+            ],
+            q_c: FieldElement::zero(),
+        })],
+        vec![],
+        vec![Witness(2)],
+    );
+    Program {
+        functions: vec![main],
+        unconstrained_functions: vec![],
+    }
+}
+
+#[test]
+fn test_prove_action_proves_the_same_statement_with_different_private_inputs_in_zk_mode() {
+    // Given
+    let mut prove_action = _prove_action();
+    prove_action.zero_knowledge = true;
+    let program = _sum_program();
+    let (circuit_data, witness_target_map, inlined_calls, computed_targets) =
+        prove_action.generate_plonky2_circuit_from_acir_program::<C>(&program);
+
+    // When
+    let proofs: Vec<_> = vec![(2, 3), (1, 4)]
+        .into_iter()
+        .map(|(x, y)| {
+            let mut witness_stack = WitnessStack::default();
+            witness_stack.push(0, witness_map_from_values(vec![(0, x), (1, y), (2, 5)]));
+            prove_action.generate_plonky2_proof_from_witness_stack(
+                &mut witness_stack,
+                &witness_target_map,
+                &inlined_calls,
+                &computed_targets,
+                &circuit_data,
+            )
+        })
+        .collect();
+
+    //Then
+    for proof in proofs {
+        assert_eq!(vec![F::from_canonical_u64(5)], proof.public_inputs);
+        assert!(circuit_data.verify(proof).is_ok());
+    }
+    let serialized_verifying_key = serialize_verifying_key(&circuit_data.verifier_data());
+    let verifier_data = deserialize_verifying_key::<C>(&serialized_verifying_key);
+    assert!(verifier_data.common.config.zero_knowledge);
+}
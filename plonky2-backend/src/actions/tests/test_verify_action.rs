@@ -0,0 +1,528 @@
+use super::*;
+use crate::actions::verify_action::VerifyAction;
+use crate::circuit_translation::emulated_bn254_translator::bn254_limbs_of;
+use crate::circuit_translation::tests::factories::plonky2_factory::*;
+use crate::noir_abi::*;
+use plonky2::field::types::Field;
+use plonky2::plonk::circuit_data::{CircuitConfig, CircuitData};
+use plonky2::plonk::config::PoseidonGoldilocksConfig;
+use serde_json::{json, Value};
+
+fn _verify_action() -> VerifyAction {
+    VerifyAction {
+        proof_path: String::new(),
+        vk_path: String::new(),
+        expected_public_inputs: None,
+        min_security_bits: None,
+    }
+}
+
+// fn main(x: Field, y: pub Field) -> pub Field
+fn _basic_div_abi() -> Value {
+    json!({
+        "parameters": [
+            {"name": "x", "type": {"kind": "field"}, "visibility": "private"},
+            {"name": "y", "type": {"kind": "field"}, "visibility": "public"}
+        ],
+        "return_type": {"abi_type": {"kind": "field"}, "visibility": "public"},
+        "error_types": {}
+    })
+}
+
+fn _basic_div_verifier_toml() -> Value {
+    parse_toml_input_values(
+        r#"
+        return = "0x7fffffff80000001"
+        y = "0x0000000000000001"
+        "#,
+    )
+}
+
+#[test]
+fn test_public_inputs_are_flattened_following_the_abi() {
+    // Given
+    let abi = _basic_div_abi();
+    let values = _basic_div_verifier_toml();
+
+    // When
+    let public_inputs = flatten_public_inputs(&abi, &values);
+
+    //Then
+    assert_eq!(
+        vec![
+            ("y".to_string(), FieldElement::one()),
+            (
+                "return".to_string(),
+                FieldElement::from(0x7fffffff80000001u128)
+            ),
+        ],
+        public_inputs
+    );
+}
+
+// fn main(x: pub i8) -> pub i8
+fn _i8_identity_abi() -> Value {
+    json!({
+        "parameters": [
+            {
+                "name": "x",
+                "type": {"kind": "integer", "sign": "signed", "width": 8},
+                "visibility": "public"
+            }
+        ],
+        "return_type": {
+            "abi_type": {"kind": "integer", "sign": "signed", "width": 8},
+            "visibility": "public"
+        },
+        "error_types": {}
+    })
+}
+
+#[test]
+fn test_negative_signed_integers_are_flattened_in_twos_complement() {
+    // Given
+    let abi = _i8_identity_abi();
+    let values = parse_toml_input_values("x = -3\nreturn = \"-128\"");
+
+    // When
+    let public_inputs = flatten_public_inputs(&abi, &values);
+
+    //Then
+    assert_eq!(
+        vec![
+            ("x".to_string(), FieldElement::from(253u128)),
+            ("return".to_string(), FieldElement::from(128u128)),
+        ],
+        public_inputs
+    );
+}
+
+#[test]
+#[should_panic(expected = "`x` does not fit in a signed integer of 8 bits")]
+fn test_negative_signed_integers_must_fit_in_their_width() {
+    // Given
+    let abi = _i8_identity_abi();
+    let values = parse_toml_input_values("x = -129\nreturn = 1");
+
+    // When
+    flatten_public_inputs(&abi, &values);
+}
+
+// fn main(x: pub u8)
+fn _u8_parameter_abi() -> Value {
+    json!({
+        "parameters": [
+            {
+                "name": "x",
+                "type": {"kind": "integer", "sign": "unsigned", "width": 8},
+                "visibility": "public"
+            }
+        ],
+        "return_type": null,
+        "error_types": {}
+    })
+}
+
+#[test]
+fn test_integers_at_the_upper_bound_of_their_width_are_accepted() {
+    // Given
+    let values = parse_toml_input_values("x = 127\nreturn = \"-128\"");
+
+    // When
+    let public_inputs = flatten_public_inputs(&_i8_identity_abi(), &values);
+    let u8_public_inputs =
+        flatten_public_inputs(&_u8_parameter_abi(), &parse_toml_input_values("x = 255"));
+
+    //Then
+    assert_eq!(FieldElement::from(127u128), public_inputs[0].1);
+    assert_eq!(
+        vec![("x".to_string(), FieldElement::from(255u128))],
+        u8_public_inputs
+    );
+}
+
+#[test]
+#[should_panic(expected = "`x` does not fit in an unsigned integer of 8 bits")]
+fn test_unsigned_integers_must_fit_in_their_width() {
+    // Given
+    let values = parse_toml_input_values("x = 300");
+
+    // When
+    flatten_public_inputs(&_u8_parameter_abi(), &values);
+}
+
+#[test]
+#[should_panic(expected = "`x` does not fit in a signed integer of 8 bits")]
+fn test_positive_signed_integers_must_fit_in_their_width() {
+    // Given
+    let values = parse_toml_input_values("x = \"200\"\nreturn = 1");
+
+    // When
+    flatten_public_inputs(&_i8_identity_abi(), &values);
+}
+
+#[test]
+#[should_panic(expected = "Invalid value for `x`: expected an integer but found 1.5")]
+fn test_float_values_are_rejected() {
+    // Given
+    let values = parse_toml_input_values("x = 1.5");
+
+    // When
+    flatten_public_inputs(&_u8_parameter_abi(), &values);
+}
+
+#[test]
+fn test_values_that_are_not_field_elements_are_reported() {
+    // Given
+    let values = vec![json!(1.5), json!("abc"), json!([1]), json!({"x": 1})];
+
+    // When
+    let results: Vec<Result<FieldElement, String>> =
+        values.iter().map(parse_field_element).collect();
+
+    //Then
+    assert_eq!(
+        vec![
+            Err("expected an integer but found 1.5".to_string()),
+            Err("invalid field element abc".to_string()),
+            Err("expected a field element but found [1]".to_string()),
+            Err("expected a field element but found {\"x\":1}".to_string()),
+        ],
+        results
+    );
+}
+
+#[test]
+fn test_public_inputs_can_be_given_as_a_json_array() {
+    // Given
+    let abi = _basic_div_abi();
+    let values = json!([1, "0x7fffffff80000001"]);
+
+    // When
+    let public_inputs = flatten_public_inputs(&abi, &values);
+
+    //Then
+    assert_eq!(
+        flatten_public_inputs(&abi, &_basic_div_verifier_toml()),
+        public_inputs
+    );
+}
+
+#[test]
+fn test_public_arrays_and_structs_are_flattened_in_order() {
+    // fn main(a: pub [u8; 2], p: pub Point, b: pub bool)
+    // Given
+    let abi = json!({
+        "parameters": [
+            {"name": "a", "type": {"kind": "array", "length": 2, "type": {"kind": "integer", "sign": "unsigned", "width": 8}}, "visibility": "public"},
+            {"name": "p", "type": {"kind": "struct", "path": "Point", "fields": [
+                {"name": "x", "type": {"kind": "field"}},
+                {"name": "y", "type": {"kind": "field"}}
+            ]}, "visibility": "public"},
+            {"name": "b", "type": {"kind": "boolean"}, "visibility": "public"}
+        ],
+        "return_type": null,
+        "error_types": {}
+    });
+    let values = parse_toml_input_values(
+        r#"
+        a = [3, "4"]
+        b = true
+        [p]
+        x = "-1"
+        y = "0x10"
+        "#,
+    );
+
+    // When
+    let public_inputs = flatten_public_inputs(&abi, &values);
+
+    //Then
+    assert_eq!(
+        vec![
+            ("a[0]".to_string(), FieldElement::from(3u128)),
+            ("a[1]".to_string(), FieldElement::from(4u128)),
+            ("p.x".to_string(), -FieldElement::one()),
+            ("p.y".to_string(), FieldElement::from(16u128)),
+            ("b".to_string(), FieldElement::one()),
+        ],
+        public_inputs
+    );
+}
+
+#[test]
+fn test_verify_accepts_proof_public_inputs_matching_the_expected_ones() {
+    // Given
+    let verify_action = _verify_action();
+    let expected_public_inputs =
+        flatten_public_inputs(&_basic_div_abi(), &_basic_div_verifier_toml());
+    let proof_public_inputs = vec![
+        F::from_canonical_u64(1),
+        F::from_canonical_u64(0x7fffffff80000001),
+    ];
+
+    // When
+    verify_action.check_public_inputs(&proof_public_inputs, expected_public_inputs, false);
+}
+
+#[test]
+#[should_panic(expected = "Public input `return` does not match")]
+fn test_verify_reports_the_name_of_the_mismatched_public_input() {
+    // Given
+    let verify_action = _verify_action();
+    let expected_public_inputs =
+        flatten_public_inputs(&_basic_div_abi(), &_basic_div_verifier_toml());
+    let proof_public_inputs = vec![F::from_canonical_u64(1), F::from_canonical_u64(5)];
+
+    // When
+    verify_action.check_public_inputs(&proof_public_inputs, expected_public_inputs, false);
+}
+
+#[test]
+#[should_panic(expected = "The proof does not have the expected amount of public inputs")]
+fn test_verify_fails_if_the_amount_of_public_inputs_differs() {
+    // Given
+    let verify_action = _verify_action();
+    let expected_public_inputs =
+        flatten_public_inputs(&_basic_div_abi(), &_basic_div_verifier_toml());
+    let proof_public_inputs = vec![F::from_canonical_u64(1)];
+
+    // When
+    verify_action.check_public_inputs(&proof_public_inputs, expected_public_inputs, false);
+}
+
+// The proof of knowing x = 3 serialized the way prove writes it
+fn _power_circuit_and_proof<C: BackendConfig>(exponent: usize) -> (CircuitData<F, C, D>, Vec<u8>) {
+    _power_circuit_and_proof_with_config(exponent, CircuitConfig::standard_recursion_config())
+}
+
+fn _power_circuit_and_proof_with_config<C: BackendConfig>(
+    exponent: usize,
+    config: CircuitConfig,
+) -> (CircuitData<F, C, D>, Vec<u8>) {
+    let circuit = power_circuit_with_config::<C>(exponent as u64, config);
+    let compressed_proof = compressed_power_proof(&circuit, 3);
+    let serialized_proof =
+        serialize_compressed_proof(&compressed_proof, &circuit.0.verifier_only.circuit_digest);
+    (circuit.0, serialized_proof)
+}
+
+#[test]
+fn test_verify_reads_back_the_verifying_key_and_proof_it_wrote() {
+    // Given
+    let (circuit_data, serialized_proof) = _power_circuit_and_proof::<C>(2);
+    let serialized_verifying_key = serialize_verifying_key(&circuit_data.verifier_data());
+
+    // When
+    let verifier_data = deserialize_verifying_key::<C>(&serialized_verifying_key);
+    let compressed_proof = deserialize_proof(&serialized_proof, &verifier_data);
+
+    //Then
+    assert_eq!(
+        vec![F::from_canonical_u64(9)],
+        compressed_proof.public_inputs
+    );
+    assert!(verifier_data.verify_compressed(compressed_proof).is_ok());
+}
+
+#[test]
+fn test_verify_reads_the_emulated_bn254_mode_from_the_verifying_key() {
+    // Given
+    let (circuit_data, _) = _power_circuit_and_proof::<C>(2);
+    let verifier_data = circuit_data.verifier_data();
+
+    // When
+    let (_, settings) =
+        deserialize_verifying_key_with_settings::<C>(&serialize_verifying_key(&verifier_data));
+    let (_, emulated_settings) = deserialize_verifying_key_with_settings::<C>(
+        &serialize_verifying_key_of_mode(&verifier_data, true),
+    );
+
+    //Then
+    assert!(!settings.emulate_bn254);
+    assert!(emulated_settings.emulate_bn254);
+}
+
+#[test]
+fn test_verify_compares_emulated_bn254_public_inputs_limb_by_limb() {
+    // Given
+    let verify_action = _verify_action();
+    let expected_public_inputs =
+        flatten_public_inputs(&_basic_div_abi(), &_basic_div_verifier_toml());
+    let proof_public_inputs: Vec<F> = expected_public_inputs
+        .iter()
+        .flat_map(|(_, value)| bn254_limbs_of(value))
+        .collect();
+
+    // When
+    verify_action.check_public_inputs(&proof_public_inputs, expected_public_inputs, true);
+}
+
+#[test]
+#[should_panic(expected = "The file is not a verifying key written by this backend")]
+fn test_verify_rejects_a_verifying_key_from_another_backend() {
+    // Given
+    let (circuit_data, _) = _power_circuit_and_proof::<C>(2);
+    let gate_serializer = DefaultGateSerializer;
+    let raw_verifying_key = circuit_data
+        .verifier_data()
+        .to_bytes(&gate_serializer)
+        .unwrap();
+
+    // When
+    deserialize_verifying_key::<C>(&raw_verifying_key);
+}
+
+#[test]
+#[should_panic(expected = "Expected a verifying key file, but found a different kind of file")]
+fn test_verify_rejects_a_proof_given_as_verifying_key() {
+    // Given
+    let (_, serialized_proof) = _power_circuit_and_proof::<C>(2);
+
+    // When
+    deserialize_verifying_key::<C>(&serialized_proof);
+}
+
+#[test]
+#[should_panic(expected = "The verifying key file has format version 7")]
+fn test_verify_rejects_a_verifying_key_with_another_format_version() {
+    // Given
+    let (circuit_data, _) = _power_circuit_and_proof::<C>(2);
+    let mut serialized_verifying_key = serialize_verifying_key(&circuit_data.verifier_data());
+    serialized_verifying_key[8..12].copy_from_slice(&7u32.to_le_bytes());
+
+    // When
+    deserialize_verifying_key::<C>(&serialized_verifying_key);
+}
+
+#[test]
+#[should_panic(expected = "The proof was generated for a different circuit than the verifying key")]
+fn test_verify_rejects_a_proof_of_another_circuit() {
+    // Given
+    let (circuit_data, _) = _power_circuit_and_proof::<C>(2);
+    let (_, serialized_proof_of_another_circuit) = _power_circuit_and_proof::<C>(3);
+
+    // When
+    deserialize_proof(
+        &serialized_proof_of_another_circuit,
+        &circuit_data.verifier_data(),
+    );
+}
+
+#[test]
+fn test_verify_reads_back_poseidon_verifying_keys_and_proofs() {
+    // Given
+    let (circuit_data, serialized_proof) = _power_circuit_and_proof::<PoseidonGoldilocksConfig>(2);
+    let serialized_verifying_key = serialize_verifying_key(&circuit_data.verifier_data());
+
+    // When
+    let verifier_data =
+        deserialize_verifying_key::<PoseidonGoldilocksConfig>(&serialized_verifying_key);
+    let compressed_proof = deserialize_proof(&serialized_proof, &verifier_data);
+
+    //Then
+    assert!(verifier_data.verify_compressed(compressed_proof).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "The proof file uses the poseidon hash config, but keccak was expected")]
+fn test_verify_rejects_a_proof_with_another_hash_config() {
+    // Given
+    let (circuit_data, _) = _power_circuit_and_proof::<C>(2);
+    let (_, poseidon_proof) = _power_circuit_and_proof::<PoseidonGoldilocksConfig>(2);
+
+    // When
+    deserialize_proof(&poseidon_proof, &circuit_data.verifier_data());
+}
+
+#[test]
+fn test_security_profiles_have_their_documented_conjectured_security() {
+    // Given
+    let profiles = vec![
+        SecurityProfile::FastDev,
+        SecurityProfile::Standard100Bit,
+        SecurityProfile::SmallProof,
+    ];
+
+    // When
+    let security_bits: Vec<usize> = profiles
+        .iter()
+        .map(|profile| conjectured_security_bits(&circuit_config(false, *profile)))
+        .collect();
+
+    //Then
+    assert_eq!(vec![30, 100, 100], security_bits);
+}
+
+#[test]
+fn test_verify_accepts_a_small_proof_profile_verifying_key_with_enough_security() {
+    // Given
+    let config = circuit_config(false, SecurityProfile::SmallProof);
+    let (circuit_data, serialized_proof) = _power_circuit_and_proof_with_config::<C>(2, config);
+    let serialized_verifying_key = serialize_verifying_key(&circuit_data.verifier_data());
+    let verify_action = _verify_action();
+
+    // When
+    let (verifier_data, security_profile) =
+        deserialize_verifying_key_with_security_profile::<C>(&serialized_verifying_key);
+    verify_action.check_minimum_security(security_profile, &verifier_data.common.config);
+
+    //Then
+    assert_eq!(Some(SecurityProfile::SmallProof), security_profile);
+    let compressed_proof = deserialize_proof(&serialized_proof, &verifier_data);
+    assert!(verifier_data.verify_compressed(compressed_proof).is_ok());
+}
+
+#[test]
+#[should_panic(
+    expected = "The verifying key was built with the fast-dev profile, which provides 30 bits of conjectured security, but at least 100 are required"
+)]
+fn test_verify_rejects_a_verifying_key_below_the_default_minimum_security() {
+    // Given
+    let config = circuit_config(false, SecurityProfile::FastDev);
+    let (circuit_data, _) = _power_circuit_and_proof_with_config::<C>(2, config);
+    let serialized_verifying_key = serialize_verifying_key(&circuit_data.verifier_data());
+    let verify_action = _verify_action();
+
+    // When
+    let (verifier_data, security_profile) =
+        deserialize_verifying_key_with_security_profile::<C>(&serialized_verifying_key);
+    verify_action.check_minimum_security(security_profile, &verifier_data.common.config);
+}
+
+#[test]
+fn test_verify_accepts_a_fast_dev_verifying_key_when_the_minimum_security_is_lowered() {
+    // Given
+    let config = circuit_config(false, SecurityProfile::FastDev);
+    let (circuit_data, _) = _power_circuit_and_proof_with_config::<C>(2, config);
+    let serialized_verifying_key = serialize_verifying_key(&circuit_data.verifier_data());
+    let mut verify_action = _verify_action();
+    verify_action.min_security_bits = Some(30);
+
+    // When
+    let (verifier_data, security_profile) =
+        deserialize_verifying_key_with_security_profile::<C>(&serialized_verifying_key);
+    verify_action.check_minimum_security(security_profile, &verifier_data.common.config);
+
+    //Then
+    assert_eq!(Some(SecurityProfile::FastDev), security_profile);
+}
+
+#[test]
+#[should_panic(expected = "its security profile does not match its FRI parameters")]
+fn test_verify_rejects_a_verifying_key_whose_security_profile_was_tampered_with() {
+    // Given
+    let config = circuit_config(false, SecurityProfile::FastDev);
+    let (circuit_data, _) = _power_circuit_and_proof_with_config::<C>(2, config);
+    let mut serialized_verifying_key = serialize_verifying_key(&circuit_data.verifier_data());
+    let serialized_verifier_data = circuit_data
+        .verifier_data()
+        .to_bytes(&DefaultGateSerializer)
+        .unwrap();
+    let security_profile_position =
+        serialized_verifying_key.len() - serialized_verifier_data.len() - 1;
+    serialized_verifying_key[security_profile_position] = SecurityProfile::Standard100Bit.id();
+
+    // When
+    deserialize_verifying_key_with_security_profile::<C>(&serialized_verifying_key);
+}
@@ -1,16 +1,97 @@
 use super::*;
+use crate::circuit_translation::bn254_arithmetic::NUM_LIMBS;
+use crate::circuit_translation::constant_analysis::field_element_to_goldilocks_field;
+use crate::circuit_translation::emulated_bn254_translator::bn254_limbs_of;
+use crate::noir_abi::*;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2::plonk::config::{KeccakGoldilocksConfig, PoseidonGoldilocksConfig};
 
 pub struct VerifyAction {
     pub proof_path: String,
     pub vk_path: String,
+    pub expected_public_inputs: Option<ExpectedPublicInputs>,
+    pub min_security_bits: Option<usize>,
+}
+
+pub struct ExpectedPublicInputs {
+    pub acir_program_json_path: String,
+    pub public_inputs_path: String,
 }
 
 impl VerifyAction {
+    // The verifying key records the hash config and the BN254 emulation it was written with
     pub fn run(&self) {
-        let verifier_data = deserialize_verifying_key_within_file_path(&self.vk_path);
+        match hash_config_within_file_path(&self.vk_path, FileKind::VerifyingKey) {
+            HashConfig::Keccak => self._run::<KeccakGoldilocksConfig>(),
+            HashConfig::Poseidon => self._run::<PoseidonGoldilocksConfig>(),
+        }
+    }
+
+    fn _run<C: BackendConfig>(&self) {
+        let (verifier_data, settings) =
+            deserialize_verifying_key_with_settings::<C>(&read_file_to_bytes(&self.vk_path));
+        self.check_minimum_security(settings.security_profile, &verifier_data.common.config);
         let compressed_proof = deserialize_proof_within_file_path(&self.proof_path, &verifier_data);
+        let public_inputs = compressed_proof.public_inputs.clone();
         verifier_data
             .verify_compressed(compressed_proof)
             .expect("Verification failed");
+
+        if let Some(expected_public_inputs) = &self.expected_public_inputs {
+            let abi =
+                deserialize_abi_within_file_path(&expected_public_inputs.acir_program_json_path);
+            let values =
+                read_input_values_within_file_path(&expected_public_inputs.public_inputs_path);
+            self.check_public_inputs(
+                &public_inputs,
+                flatten_public_inputs(&abi, &values),
+                settings.emulate_bn254,
+            );
+        }
+    }
+
+    pub fn check_minimum_security(
+        &self,
+        security_profile: Option<SecurityProfile>,
+        config: &CircuitConfig,
+    ) {
+        check_minimum_security(
+            "The verifying key",
+            security_profile,
+            config,
+            self.min_security_bits,
+        );
+    }
+
+    pub fn check_public_inputs(
+        &self,
+        public_inputs: &Vec<F>,
+        expected_public_inputs: Vec<(String, FieldElement)>,
+        emulate_bn254: bool,
+    ) {
+        let targets_per_public_input = match emulate_bn254 {
+            true => NUM_LIMBS,
+            false => 1,
+        };
+        assert_eq!(
+            public_inputs.len(),
+            expected_public_inputs.len() * targets_per_public_input,
+            "The proof does not have the expected amount of public inputs"
+        );
+        for ((name, expected_value), public_input) in expected_public_inputs
+            .iter()
+            .zip(public_inputs.chunks(targets_per_public_input))
+        {
+            let expected_public_input = match emulate_bn254 {
+                true => bn254_limbs_of(expected_value),
+                false => vec![field_element_to_goldilocks_field(expected_value)],
+            };
+            if expected_public_input != public_input {
+                panic!(
+                    "Public input `{}` does not match: expected {}, found {:?}",
+                    name, expected_value, public_input
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,76 @@
+use super::*;
+use crate::actions::aggregate_action::read_aggregation_verifying_key;
+use crate::proof_aggregation::*;
+
+pub struct VerifyAggregateAction {
+    pub vk_paths: Vec<String>,
+    pub aggregated_proof_path: String,
+    pub min_security_bits: Option<usize>,
+}
+
+impl VerifyAggregateAction {
+    pub fn run(&self) {
+        let verifier_data: Vec<VerifierCircuitData<F, AggregationConfig, D>> = self
+            .vk_paths
+            .iter()
+            .map(|vk_path| read_aggregation_verifying_key(vk_path, self.min_security_bits))
+            .collect();
+        let aggregated_proof = read_file_to_bytes(&self.aggregated_proof_path);
+        let included_programs = self.verify(&verifier_data, &aggregated_proof);
+        for (index, (program, leaf)) in included_programs.iter().enumerate() {
+            println!(
+                "Proof {}: {} with public inputs {:?}",
+                index, self.vk_paths[*program], leaf.public_inputs
+            );
+        }
+    }
+
+    // Every leaf must be a proof of one of the given programs. The aggregation tree is built
+    // again from their verifying keys, so the root proof can only verify if each leaf is a proof
+    // of the program it claims. Returns the program of each leaf, as an index into verifier_data
+    pub fn verify(
+        &self,
+        verifier_data: &Vec<VerifierCircuitData<F, AggregationConfig, D>>,
+        aggregated_proof: &[u8],
+    ) -> Vec<(usize, AggregatedProofLeaf)> {
+        let leaves = deserialize_aggregated_proof_leaves(aggregated_proof);
+        let programs: Vec<usize> = leaves
+            .iter()
+            .enumerate()
+            .map(|(index, leaf)| self._program_of(verifier_data, index, leaf))
+            .collect();
+        let leaf_verifier_data = programs
+            .iter()
+            .map(|program| &verifier_data[*program])
+            .collect();
+        let tree = build_aggregation_tree(&leaf_verifier_data);
+        let root_verifier_data = tree.root_verifier_data();
+        let compressed_proof = deserialize_aggregated_proof(aggregated_proof, &root_verifier_data);
+        if compressed_proof.public_inputs != leaves_commitment(&leaves) {
+            panic!("The aggregated proof does not commit to the leaves it lists");
+        }
+        root_verifier_data
+            .verify_compressed(compressed_proof)
+            .expect("Verification failed");
+        programs.into_iter().zip(leaves).collect()
+    }
+
+    fn _program_of(
+        &self,
+        verifier_data: &Vec<VerifierCircuitData<F, AggregationConfig, D>>,
+        index: usize,
+        leaf: &AggregatedProofLeaf,
+    ) -> usize {
+        verifier_data
+            .iter()
+            .position(|program_verifier_data| {
+                program_verifier_data.verifier_only.circuit_digest == leaf.circuit_digest
+            })
+            .unwrap_or_else(|| {
+                panic!(
+                    "Proof {} of the aggregated proof is not a proof of any of the given programs",
+                    index
+                )
+            })
+    }
+}
@@ -0,0 +1,28 @@
+use super::*;
+use crate::circuit_translation::recursive_aggregation_translator::{
+    key_hash, verifier_data_fields,
+};
+use plonky2::plonk::config::PoseidonGoldilocksConfig;
+
+pub struct VkAsFieldsAction {
+    pub vk_path: String,
+    pub resulting_fields_path: String,
+}
+
+impl VkAsFieldsAction {
+    pub fn run(&self) {
+        let verifier_data =
+            deserialize_verifying_key_within_file_path::<PoseidonGoldilocksConfig>(&self.vk_path);
+        let fields = self.vk_as_fields(&verifier_data);
+        write_fields_as_json_to_file_path(&fields, &self.resulting_fields_path);
+    }
+
+    // Like Barretenberg's vk_as_fields, the key hash goes first and the verifying key follows
+    pub fn vk_as_fields(
+        &self,
+        verifier_data: &VerifierCircuitData<F, PoseidonGoldilocksConfig, D>,
+    ) -> Vec<F> {
+        let verification_key = verifier_data_fields(&verifier_data.verifier_only);
+        [vec![key_hash(&verification_key)], verification_key].concat()
+    }
+}
@@ -0,0 +1,52 @@
+use super::*;
+use crate::noir_abi::abi_return_witnesses;
+use plonky2::plonk::config::{KeccakGoldilocksConfig, PoseidonGoldilocksConfig};
+
+pub struct WritePKAction {
+    pub acir_program_json_path: String,
+    pub pk_path_output: String,
+    pub hash_config: HashConfig,
+    pub zero_knowledge: bool,
+    pub security_profile: SecurityProfile,
+    pub recursive_vk_paths: Vec<String>,
+}
+
+impl WritePKAction {
+    pub fn run(&self) {
+        match self.hash_config {
+            HashConfig::Keccak => self._run::<KeccakGoldilocksConfig>(),
+            HashConfig::Poseidon => self._run::<PoseidonGoldilocksConfig>(),
+        }
+    }
+
+    fn _run<C: BackendConfig>(&self) {
+        let acir_program: Program =
+            deserialize_program_within_file_path(&self.acir_program_json_path);
+        let mut translator = CircuitBuilderFromAcirToPlonky2::new_with_config(circuit_config(
+            self.zero_knowledge,
+            self.security_profile,
+        ));
+        translator.recursive_common_data =
+            deserialize_recursive_common_data_within_file_paths(&self.recursive_vk_paths);
+        translator.return_witnesses = abi_return_witnesses(&deserialize_abi_within_file_path(
+            &self.acir_program_json_path,
+        ));
+        translator.translate_program(&acir_program);
+        let (circuit_data, witness_target_map, inlined_calls, computed_targets) =
+            translator.unpack_program::<C>();
+        write_proving_key_to_file_path(
+            &acir_program,
+            &ProvingKeySettings::new(
+                self.zero_knowledge,
+                self.security_profile,
+                false,
+                &self.recursive_vk_paths,
+            ),
+            &circuit_data,
+            &witness_target_map,
+            &inlined_calls,
+            &computed_targets,
+            &self.pk_path_output,
+        );
+    }
+}
@@ -1,25 +1,56 @@
 use super::*;
+use crate::circuit_translation::emulated_bn254_translator::{
+    check_emulated_bn254_support, CircuitBuilderFromAcirToEmulatedBn254,
+};
+use crate::noir_abi::abi_return_witnesses;
+use plonky2::plonk::config::{KeccakGoldilocksConfig, PoseidonGoldilocksConfig};
 
 pub struct WriteVKAction {
     pub acir_program_json_path: String,
     pub vk_path_output: String,
+    pub emulate_bn254: bool,
+    pub hash_config: HashConfig,
+    pub zero_knowledge: bool,
+    pub security_profile: SecurityProfile,
+    pub recursive_vk_paths: Vec<String>,
 }
 
 impl WriteVKAction {
     pub fn run(&self) {
+        match self.hash_config {
+            HashConfig::Keccak => self._run::<KeccakGoldilocksConfig>(),
+            HashConfig::Poseidon => self._run::<PoseidonGoldilocksConfig>(),
+        }
+    }
+
+    fn _run<C: BackendConfig>(&self) {
         let acir_program: Program =
             deserialize_program_within_file_path(&self.acir_program_json_path);
-        let acir_circuit = &acir_program.functions[0];
-        let mut translator = CircuitBuilderFromAcirToPlonky2::new();
-        translator.translate_circuit(acir_circuit);
-        let CircuitBuilderFromAcirToPlonky2 {
-            builder,
-            witness_target_map: _,
-        } = translator;
-        let plonky2_circuit = builder.build::<C>();
+        let config = circuit_config(self.zero_knowledge, self.security_profile);
+        let return_witnesses = abi_return_witnesses(&deserialize_abi_within_file_path(
+            &self.acir_program_json_path,
+        ));
+        let plonky2_circuit = match self.emulate_bn254 {
+            true => {
+                check_emulated_bn254_support(&acir_program);
+                let mut translator = CircuitBuilderFromAcirToEmulatedBn254::new_with_config(config);
+                translator.return_witnesses = return_witnesses;
+                translator.translate_circuit(&acir_program.functions[0]);
+                translator.unpack::<C>().0
+            }
+            false => {
+                let mut translator = CircuitBuilderFromAcirToPlonky2::new_with_config(config);
+                translator.recursive_common_data =
+                    deserialize_recursive_common_data_within_file_paths(&self.recursive_vk_paths);
+                translator.return_witnesses = return_witnesses;
+                translator.translate_program(&acir_program);
+                translator.unpack::<C>().0
+            }
+        };
         let verifier_data = plonky2_circuit.verifier_data();
-        let gate_serializer = DefaultGateSerializer;
-        let serialized_verifier_data = verifier_data.to_bytes(&gate_serializer).unwrap();
-        write_bytes_to_file_path(serialized_verifier_data, &self.vk_path_output);
+        write_bytes_to_file_path(
+            serialize_verifying_key_of_mode(&verifier_data, self.emulate_bn254),
+            &self.vk_path_output,
+        );
     }
 }
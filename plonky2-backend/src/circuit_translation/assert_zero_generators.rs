@@ -0,0 +1,101 @@
+use super::*;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
+use plonky2::iop::witness::{PartitionWitness, Witness as WitnessRead, WitnessWrite};
+use plonky2::plonk::circuit_data::CommonCircuitData;
+use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
+
+// Solves the only unknown of an expression like the ACVM does: once every other witness
+// is known, the expression is linear in the unknown, a * unknown + b = 0
+#[derive(Debug, Clone, Default)]
+pub struct AssertZeroSolverGenerator {
+    pub mul_terms: Vec<(F, Target, Target)>,
+    pub linear_combinations: Vec<(F, Target)>,
+    pub constant: F,
+    pub unknown: Target,
+}
+
+impl SimpleGenerator<F, D> for AssertZeroSolverGenerator {
+    fn id(&self) -> String {
+        "AssertZeroSolverGenerator".to_string()
+    }
+
+    fn dependencies(&self) -> Vec<Target> {
+        let mul_term_targets = self
+            .mul_terms
+            .iter()
+            .flat_map(|(_, lhs, rhs)| vec![*lhs, *rhs]);
+        let linear_targets = self.linear_combinations.iter().map(|(_, target)| *target);
+        mul_term_targets
+            .chain(linear_targets)
+            .filter(|target| *target != self.unknown)
+            .collect()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let mut unknown_coefficient = F::ZERO;
+        let mut known_part = self.constant;
+        for (coefficient, lhs, rhs) in &self.mul_terms {
+            if *lhs == self.unknown {
+                unknown_coefficient += *coefficient * witness.get_target(*rhs);
+            } else if *rhs == self.unknown {
+                unknown_coefficient += *coefficient * witness.get_target(*lhs);
+            } else {
+                known_part += *coefficient * witness.get_target(*lhs) * witness.get_target(*rhs);
+            }
+        }
+        for (coefficient, target) in &self.linear_combinations {
+            if *target == self.unknown {
+                unknown_coefficient += *coefficient;
+            } else {
+                known_part += *coefficient * witness.get_target(*target);
+            }
+        }
+        if unknown_coefficient.is_zero() {
+            panic!(
+                "Cannot solve the witness of target {:?}, its coefficient is zero for the given witness values",
+                self.unknown
+            );
+        }
+        out_buffer.set_target(self.unknown, -known_part / unknown_coefficient);
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        let mul_term_coefficients: Vec<F> = self.mul_terms.iter().map(|(c, _, _)| *c).collect();
+        let mul_term_lhs: Vec<Target> = self.mul_terms.iter().map(|(_, lhs, _)| *lhs).collect();
+        let mul_term_rhs: Vec<Target> = self.mul_terms.iter().map(|(_, _, rhs)| *rhs).collect();
+        let linear_coefficients: Vec<F> =
+            self.linear_combinations.iter().map(|(c, _)| *c).collect();
+        let linear_targets: Vec<Target> =
+            self.linear_combinations.iter().map(|(_, t)| *t).collect();
+        dst.write_field_vec(&mul_term_coefficients)?;
+        dst.write_target_vec(&mul_term_lhs)?;
+        dst.write_target_vec(&mul_term_rhs)?;
+        dst.write_field_vec(&linear_coefficients)?;
+        dst.write_target_vec(&linear_targets)?;
+        dst.write_field(self.constant)?;
+        dst.write_target(self.unknown)
+    }
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let mul_term_coefficients: Vec<F> = src.read_field_vec()?;
+        let mul_term_lhs = src.read_target_vec()?;
+        let mul_term_rhs = src.read_target_vec()?;
+        let linear_coefficients: Vec<F> = src.read_field_vec()?;
+        let linear_targets = src.read_target_vec()?;
+        let mul_terms = mul_term_coefficients
+            .into_iter()
+            .zip(mul_term_lhs)
+            .zip(mul_term_rhs)
+            .map(|((coefficient, lhs), rhs)| (coefficient, lhs, rhs))
+            .collect();
+        Ok(Self {
+            mul_terms,
+            linear_combinations: linear_coefficients
+                .into_iter()
+                .zip(linear_targets)
+                .collect(),
+            constant: src.read_field()?,
+            unknown: src.read_target()?,
+        })
+    }
+}
@@ -1,8 +1,12 @@
 use super::*;
+use crate::circuit_translation::assert_zero_generators::AssertZeroSolverGenerator;
+use crate::circuit_translation::constant_analysis::field_element_to_goldilocks_field;
+use std::collections::BTreeSet;
 
 pub struct AssertZeroTranslator<'a> {
     builder: &'a mut CircuitBuilder<F, D>,
     witness_target_map: &'a mut HashMap<Witness, Target>,
+    computed_targets: &'a mut HashSet<Target>,
     expression: &'a Expression,
 }
 
@@ -10,49 +14,112 @@ impl<'a> AssertZeroTranslator<'a> {
     pub fn new_for(
         builder: &'a mut CircuitBuilder<F, D>,
         witness_target_map: &'a mut HashMap<Witness, Target>,
+        computed_targets: &'a mut HashSet<Target>,
         expression: &'a Expression,
     ) -> AssertZeroTranslator<'a> {
         Self {
             builder,
             witness_target_map,
+            computed_targets,
             expression,
         }
     }
 
-    pub fn translate(&mut self) {
+    // The ACVM solves an expression for its only witness whose value is not available yet
+    pub fn translate(&mut self, known_witnesses: &HashSet<Witness>) {
+        let unknown_witnesses: Vec<Witness> = self
+            .witnesses()
+            .into_iter()
+            .filter(|witness| !known_witnesses.contains(witness))
+            .collect();
+        let expression_target = self.translate_expression();
+        self.builder.assert_zero(expression_target);
+        if let [unknown_witness] = unknown_witnesses[..] {
+            self._add_solver_generator_for(unknown_witness);
+        }
+    }
+
+    pub fn translate_expression(&mut self) -> Target {
         self._register_intermediate_witnesses_for_assert_zero();
-        self._translate_assert_zero();
+        self._translate_expression()
+    }
+
+    pub fn witnesses(self: &Self) -> BTreeSet<Witness> {
+        let mul_term_witnesses = self
+            .expression
+            .mul_terms
+            .iter()
+            .flat_map(|(_, lhs, rhs)| vec![*lhs, *rhs]);
+        let linear_witnesses = self
+            .expression
+            .linear_combinations
+            .iter()
+            .map(|(_, witness)| *witness);
+        mul_term_witnesses.chain(linear_witnesses).collect()
     }
 
-    fn _get_or_create_target_for_witness(self: &mut Self, witness: Witness) -> Target {
-        match self.witness_target_map.get(&witness) {
-            Some(target) => *target,
-            None => {
-                let target = self.builder.add_virtual_target();
-                self.witness_target_map.insert(witness, target);
-                target
-            }
+    // A witness multiplied by itself cannot be solved for, so no generator is added then
+    fn _add_solver_generator_for(self: &mut Self, unknown_witness: Witness) {
+        let is_squared = self
+            .expression
+            .mul_terms
+            .iter()
+            .any(|(_, lhs, rhs)| *lhs == unknown_witness && *rhs == unknown_witness);
+        if is_squared {
+            return;
         }
+        let mul_terms = self
+            .expression
+            .mul_terms
+            .iter()
+            .map(|(coefficient, lhs, rhs)| {
+                (
+                    field_element_to_goldilocks_field(coefficient),
+                    self.witness_target_map[lhs],
+                    self.witness_target_map[rhs],
+                )
+            })
+            .collect();
+        let linear_combinations = self
+            .expression
+            .linear_combinations
+            .iter()
+            .map(|(coefficient, witness)| {
+                (
+                    field_element_to_goldilocks_field(coefficient),
+                    self.witness_target_map[witness],
+                )
+            })
+            .collect();
+        let unknown = self.witness_target_map[&unknown_witness];
+        self.builder
+            .add_simple_generator(AssertZeroSolverGenerator {
+                mul_terms,
+                linear_combinations,
+                constant: field_element_to_goldilocks_field(&self.expression.q_c),
+                unknown,
+            });
+        self.computed_targets.insert(unknown);
     }
 
     fn _register_intermediate_witnesses_for_assert_zero(&mut self) {
         for (_, witness_1, witness_2) in &self.expression.mul_terms {
-            self._get_or_create_target_for_witness(*witness_1);
-            self._get_or_create_target_for_witness(*witness_2);
+            get_or_create_target_for_witness(self.builder, self.witness_target_map, *witness_1);
+            get_or_create_target_for_witness(self.builder, self.witness_target_map, *witness_2);
         }
         for (_, witness) in &self.expression.linear_combinations {
-            self._get_or_create_target_for_witness(*witness);
+            get_or_create_target_for_witness(self.builder, self.witness_target_map, *witness);
         }
     }
 
-    fn _translate_assert_zero(self: &mut Self) {
-        let g_constant = self._field_element_to_goldilocks_field(&self.expression.q_c);
+    fn _translate_expression(self: &mut Self) -> Target {
+        let g_constant = field_element_to_goldilocks_field(&self.expression.q_c);
 
         let constant_target = self.builder.constant(g_constant);
         let mut current_acc_target = constant_target;
         current_acc_target = self._add_linear_combinations(current_acc_target);
         current_acc_target = self._add_cuadratic_combinations(current_acc_target);
-        self.builder.assert_zero(current_acc_target);
+        current_acc_target
     }
 
     fn _add_cuadratic_combinations(self: &mut Self, mut current_acc_target: Target) -> Target {
@@ -89,7 +156,7 @@ impl<'a> AssertZeroTranslator<'a> {
         public_input_witness: &Witness,
     ) -> Target {
         let factor_target = *self.witness_target_map.get(public_input_witness).unwrap();
-        let g_first_pi_factor = self._field_element_to_goldilocks_field(f_multiply_constant_factor);
+        let g_first_pi_factor = field_element_to_goldilocks_field(f_multiply_constant_factor);
         self.builder.mul_const(g_first_pi_factor, factor_target)
     }
 
@@ -99,7 +166,7 @@ impl<'a> AssertZeroTranslator<'a> {
         public_input_witness_1: &Witness,
         public_input_witness_2: &Witness,
     ) -> Target {
-        let g_cuadratic_factor = self._field_element_to_goldilocks_field(f_cuadratic_factor);
+        let g_cuadratic_factor = field_element_to_goldilocks_field(f_cuadratic_factor);
         let first_public_input_target =
             *self.witness_target_map.get(public_input_witness_1).unwrap();
         let second_public_input_target =
@@ -110,9 +177,4 @@ impl<'a> AssertZeroTranslator<'a> {
             .mul(first_public_input_target, second_public_input_target);
         self.builder.mul_const(g_cuadratic_factor, cuadratic_target)
     }
-
-    fn _field_element_to_goldilocks_field(self: &mut Self, fe: &FieldElement) -> F {
-        let fe_as_big_uint = BigUint::from_bytes_be(&fe.to_be_bytes() as &[u8]);
-        F::from_noncanonical_biguint(fe_as_big_uint)
-    }
 }
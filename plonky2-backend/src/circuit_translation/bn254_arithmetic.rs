@@ -0,0 +1,194 @@
+use super::*;
+use crate::circuit_translation::bn254_generators::{
+    Bn254CarriesGenerator, Bn254ComplementGenerator, Bn254ReductionGenerator,
+};
+use crate::circuit_translation::targets::Bn254Target;
+use plonky2::field::types::PrimeField64;
+
+pub const LIMB_BITS: usize = 16;
+pub const NUM_LIMBS: usize = 16;
+// Carries live in [-2^46, 2^46), so column equations never wrap around the Goldilocks modulus
+const CARRY_BITS: usize = 47;
+const CARRY_OFFSET: u64 = 1 << 46;
+pub const MAX_TERMS_PER_EXPRESSION: usize = 1 << 20;
+
+pub fn bn254_modulus() -> BigUint {
+    FieldElement::modulus()
+}
+
+pub fn field_element_to_biguint(fe: &FieldElement) -> BigUint {
+    BigUint::from_bytes_be(&fe.to_be_bytes())
+}
+
+pub fn biguint_to_limbs(value: &BigUint, num_limbs: usize) -> Vec<F> {
+    let bytes = value.to_bytes_le();
+    (0..num_limbs)
+        .map(|position| {
+            let low_byte = *bytes.get(2 * position).unwrap_or(&0) as u64;
+            let high_byte = *bytes.get(2 * position + 1).unwrap_or(&0) as u64;
+            F::from_canonical_u64(low_byte + (high_byte << 8))
+        })
+        .collect()
+}
+
+pub fn limbs_to_biguint(limbs: &[F]) -> BigUint {
+    limbs
+        .iter()
+        .rev()
+        .fold(BigUint::from(0u32), |accumulated, limb| {
+            (accumulated << LIMB_BITS) + limb.to_canonical_u64()
+        })
+}
+
+// Values are accumulated in columns: a column vector c represents the integer sum(c_i * 2^(16 i)).
+// Every relation modulo p is checked as an integer identity over columns using carries.
+pub struct Bn254Arithmetic<'a> {
+    builder: &'a mut CircuitBuilder<F, D>,
+}
+
+impl<'a> Bn254Arithmetic<'a> {
+    pub fn new_for(builder: &'a mut CircuitBuilder<F, D>) -> Bn254Arithmetic<'a> {
+        Self { builder }
+    }
+
+    pub fn add_virtual_bn254_target(self: &mut Self) -> Bn254Target {
+        Bn254Target {
+            limbs: self._add_virtual_limbs(NUM_LIMBS),
+        }
+    }
+
+    pub fn zero_columns(self: &mut Self) -> Vec<Target> {
+        vec![self.builder.zero(); 2 * NUM_LIMBS - 1]
+    }
+
+    pub fn mul(self: &mut Self, a: &Bn254Target, b: &Bn254Target) -> Bn254Target {
+        let mut columns = self.zero_columns();
+        for (i, a_limb) in a.limbs.iter().enumerate() {
+            for (j, b_limb) in b.limbs.iter().enumerate() {
+                columns[i + j] = self.builder.mul_add(*a_limb, *b_limb, columns[i + j]);
+            }
+        }
+        self._reduce(columns)
+    }
+
+    pub fn add_scaled(
+        self: &mut Self,
+        columns: &mut Vec<Target>,
+        coefficient: &FieldElement,
+        x: &Bn254Target,
+    ) {
+        let coefficient_limbs = biguint_to_limbs(&field_element_to_biguint(coefficient), NUM_LIMBS);
+        for (i, coefficient_limb) in coefficient_limbs.iter().enumerate() {
+            if coefficient_limb.is_zero() {
+                continue;
+            }
+            for (j, x_limb) in x.limbs.iter().enumerate() {
+                columns[i + j] =
+                    self.builder
+                        .mul_const_add(*coefficient_limb, *x_limb, columns[i + j]);
+            }
+        }
+    }
+
+    pub fn add_constant(self: &mut Self, columns: &mut Vec<Target>, constant: &FieldElement) {
+        let constant_limbs = biguint_to_limbs(&field_element_to_biguint(constant), NUM_LIMBS);
+        for (i, constant_limb) in constant_limbs.into_iter().enumerate() {
+            columns[i] = self.builder.add_const(columns[i], constant_limb);
+        }
+    }
+
+    pub fn assert_zero_mod_p(self: &mut Self, columns: Vec<Target>) {
+        let remainder = self._reduce(columns);
+        for limb in remainder.limbs {
+            self.builder.assert_zero(limb);
+        }
+    }
+
+    // x + (p - 1 - x) = p - 1 with both terms non-negative implies x < p
+    pub fn assert_canonical(self: &mut Self, x: &Bn254Target) {
+        let complement = self.add_virtual_bn254_target();
+        self.builder.add_simple_generator(Bn254ComplementGenerator {
+            x: x.limbs.clone(),
+            complement: complement.limbs.clone(),
+        });
+
+        let modulus_minus_one = bn254_modulus() - 1u32;
+        let modulus_minus_one_limbs = biguint_to_limbs(&modulus_minus_one, NUM_LIMBS);
+        let columns: Vec<Target> = (0..NUM_LIMBS)
+            .map(|i| {
+                let sum = self.builder.add(x.limbs[i], complement.limbs[i]);
+                self.builder.add_const(sum, -modulus_minus_one_limbs[i])
+            })
+            .collect();
+        self._assert_columns_represent_zero(columns);
+    }
+
+    fn _add_virtual_limbs(self: &mut Self, num_limbs: usize) -> Vec<Target> {
+        (0..num_limbs)
+            .map(|_| {
+                let limb = self.builder.add_virtual_target();
+                self.builder.range_check(limb, LIMB_BITS);
+                limb
+            })
+            .collect()
+    }
+
+    // Returns r with columns = q * p + r for a witnessed quotient q
+    fn _reduce(self: &mut Self, columns: Vec<Target>) -> Bn254Target {
+        let quotient = self._add_virtual_limbs(columns.len() + 4 - NUM_LIMBS);
+        let remainder = self.add_virtual_bn254_target();
+        self.builder.add_simple_generator(Bn254ReductionGenerator {
+            columns: columns.clone(),
+            quotient: quotient.clone(),
+            remainder: remainder.limbs.clone(),
+        });
+
+        let modulus_limbs = biguint_to_limbs(&bn254_modulus(), NUM_LIMBS);
+        let mut difference = columns;
+        let zero = self.builder.zero();
+        difference.resize(quotient.len() + NUM_LIMBS - 1, zero);
+        for (i, quotient_limb) in quotient.iter().enumerate() {
+            for (j, modulus_limb) in modulus_limbs.iter().enumerate() {
+                if modulus_limb.is_zero() {
+                    continue;
+                }
+                difference[i + j] =
+                    self.builder
+                        .mul_const_add(-*modulus_limb, *quotient_limb, difference[i + j]);
+            }
+        }
+        for (i, remainder_limb) in remainder.limbs.iter().enumerate() {
+            difference[i] = self.builder.sub(difference[i], *remainder_limb);
+        }
+        self._assert_columns_represent_zero(difference);
+        remainder
+    }
+
+    fn _assert_columns_represent_zero(self: &mut Self, columns: Vec<Target>) {
+        let carries: Vec<Target> = (0..columns.len() - 1)
+            .map(|_| {
+                let carry = self.builder.add_virtual_target();
+                let offset_carry = self
+                    .builder
+                    .add_const(carry, F::from_canonical_u64(CARRY_OFFSET));
+                self.builder.range_check(offset_carry, CARRY_BITS);
+                carry
+            })
+            .collect();
+        self.builder.add_simple_generator(Bn254CarriesGenerator {
+            columns: columns.clone(),
+            carries: carries.clone(),
+        });
+
+        let limb_base = F::from_canonical_u64(1 << LIMB_BITS);
+        let zero = self.builder.zero();
+        for (i, column) in columns.iter().enumerate() {
+            let incoming_carry = if i == 0 { zero } else { carries[i - 1] };
+            let outgoing_carry = if i < carries.len() { carries[i] } else { zero };
+            let column_with_carry = self.builder.add(*column, incoming_carry);
+            let shifted_outgoing_carry = self.builder.mul_const(limb_base, outgoing_carry);
+            let difference = self.builder.sub(column_with_carry, shifted_outgoing_carry);
+            self.builder.assert_zero(difference);
+        }
+    }
+}
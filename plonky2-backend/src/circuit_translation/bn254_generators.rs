@@ -0,0 +1,142 @@
+use super::*;
+use crate::circuit_translation::bn254_arithmetic::{
+    biguint_to_limbs, bn254_modulus, limbs_to_biguint, LIMB_BITS, NUM_LIMBS,
+};
+use plonky2::field::types::PrimeField64;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
+use plonky2::iop::witness::{PartitionWitness, Witness as WitnessRead, WitnessWrite};
+use plonky2::plonk::circuit_data::CommonCircuitData;
+use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
+
+#[derive(Debug, Clone, Default)]
+pub struct Bn254ReductionGenerator {
+    pub columns: Vec<Target>,
+    pub quotient: Vec<Target>,
+    pub remainder: Vec<Target>,
+}
+
+impl SimpleGenerator<F, D> for Bn254ReductionGenerator {
+    fn id(&self) -> String {
+        "Bn254ReductionGenerator".to_string()
+    }
+
+    fn dependencies(&self) -> Vec<Target> {
+        self.columns.clone()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let value = limbs_to_biguint(&witness.get_targets(&self.columns));
+        let modulus = bn254_modulus();
+        let quotient = &value / &modulus;
+        let remainder = &value % &modulus;
+        out_buffer.set_target_arr(
+            &self.quotient,
+            &biguint_to_limbs(&quotient, self.quotient.len()),
+        );
+        out_buffer.set_target_arr(&self.remainder, &biguint_to_limbs(&remainder, NUM_LIMBS));
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_vec(&self.columns)?;
+        dst.write_target_vec(&self.quotient)?;
+        dst.write_target_vec(&self.remainder)
+    }
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        Ok(Self {
+            columns: src.read_target_vec()?,
+            quotient: src.read_target_vec()?,
+            remainder: src.read_target_vec()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Bn254CarriesGenerator {
+    pub columns: Vec<Target>,
+    pub carries: Vec<Target>,
+}
+
+impl SimpleGenerator<F, D> for Bn254CarriesGenerator {
+    fn id(&self) -> String {
+        "Bn254CarriesGenerator".to_string()
+    }
+
+    fn dependencies(&self) -> Vec<Target> {
+        self.columns.clone()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let mut carry: i128 = 0;
+        for (column, carry_target) in self.columns.iter().zip(self.carries.iter()) {
+            let column_value = _field_to_signed(witness.get_target(*column));
+            carry = (column_value + carry) >> LIMB_BITS;
+            out_buffer.set_target(*carry_target, _signed_to_field(carry));
+        }
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_vec(&self.columns)?;
+        dst.write_target_vec(&self.carries)
+    }
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        Ok(Self {
+            columns: src.read_target_vec()?,
+            carries: src.read_target_vec()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Bn254ComplementGenerator {
+    pub x: Vec<Target>,
+    pub complement: Vec<Target>,
+}
+
+impl SimpleGenerator<F, D> for Bn254ComplementGenerator {
+    fn id(&self) -> String {
+        "Bn254ComplementGenerator".to_string()
+    }
+
+    fn dependencies(&self) -> Vec<Target> {
+        self.x.clone()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let x = limbs_to_biguint(&witness.get_targets(&self.x));
+        let modulus_minus_one = bn254_modulus() - 1u32;
+        let complement = match x <= modulus_minus_one {
+            true => modulus_minus_one - x,
+            false => BigUint::from(0u32),
+        };
+        out_buffer.set_target_arr(&self.complement, &biguint_to_limbs(&complement, NUM_LIMBS));
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_vec(&self.x)?;
+        dst.write_target_vec(&self.complement)
+    }
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        Ok(Self {
+            x: src.read_target_vec()?,
+            complement: src.read_target_vec()?,
+        })
+    }
+}
+
+fn _field_to_signed(value: F) -> i128 {
+    let value = value.to_canonical_u64();
+    match value > F::ORDER / 2 {
+        true => value as i128 - F::ORDER as i128,
+        false => value as i128,
+    }
+}
+
+fn _signed_to_field(value: i128) -> F {
+    match value >= 0 {
+        true => F::from_canonical_u64(value as u64),
+        false => -F::from_canonical_u64((-value) as u64),
+    }
+}
@@ -0,0 +1,80 @@
+use super::*;
+use acir::circuit::brillig::BrilligInputs;
+use plonky2::field::types::PrimeField64;
+
+// Noir encodes negative numbers as p_bn254 - x, so those are mapped to -x in Goldilocks.
+// Any other constant above the Goldilocks modulus would silently change its meaning.
+pub fn field_element_to_goldilocks_field(fe: &FieldElement) -> F {
+    let fe_as_big_uint = BigUint::from_bytes_be(&fe.to_be_bytes() as &[u8]);
+    let goldilocks_order = BigUint::from(F::ORDER);
+    if fe_as_big_uint >= goldilocks_order {
+        let negated_fe = FieldElement::modulus() - &fe_as_big_uint;
+        if negated_fe < goldilocks_order {
+            return -F::from_noncanonical_biguint(negated_fe);
+        }
+    }
+    F::from_noncanonical_biguint(fe_as_big_uint)
+}
+
+pub fn fits_in_goldilocks_field(fe: &FieldElement) -> bool {
+    let fe_as_big_uint = BigUint::from_bytes_be(&fe.to_be_bytes() as &[u8]);
+    let goldilocks_order = BigUint::from(F::ORDER);
+    fe_as_big_uint < goldilocks_order || FieldElement::modulus() - fe_as_big_uint < goldilocks_order
+}
+
+pub fn opcodes_with_constants_not_fitting_in_goldilocks_field(circuit: &Circuit) -> Vec<usize> {
+    circuit
+        .opcodes
+        .iter()
+        .enumerate()
+        .filter(|(_, opcode)| !_opcode_constants_fit_in_goldilocks_field(opcode))
+        .map(|(opcode_index, _)| opcode_index)
+        .collect()
+}
+
+fn _opcode_constants_fit_in_goldilocks_field(opcode: &Opcode) -> bool {
+    _expressions_of(opcode)
+        .into_iter()
+        .all(_expression_constants_fit_in_goldilocks_field)
+}
+
+// Black box inputs are witnesses in this ACIR version, so they carry no constants
+fn _expressions_of(opcode: &Opcode) -> Vec<&Expression> {
+    match opcode {
+        Opcode::AssertZero(expr) => vec![expr],
+        Opcode::MemoryOp {
+            block_id: _,
+            op,
+            predicate,
+        } => {
+            let mut expressions = vec![&op.index, &op.value, &op.operation];
+            expressions.extend(predicate.iter());
+            expressions
+        }
+        Opcode::BrilligCall {
+            inputs, predicate, ..
+        } => inputs
+            .iter()
+            .flat_map(|input| match input {
+                BrilligInputs::Single(expr) => vec![expr],
+                BrilligInputs::Array(exprs) => exprs.iter().collect(),
+                BrilligInputs::MemoryArray(_) => vec![],
+            })
+            .chain(predicate.iter())
+            .collect(),
+        Opcode::Call { predicate, .. } => predicate.iter().collect(),
+        Opcode::BlackBoxFuncCall(_) | Opcode::MemoryInit { .. } => vec![],
+    }
+}
+
+fn _expression_constants_fit_in_goldilocks_field(expr: &Expression) -> bool {
+    fits_in_goldilocks_field(&expr.q_c)
+        && expr
+            .mul_terms
+            .iter()
+            .all(|(coefficient, _, _)| fits_in_goldilocks_field(coefficient))
+        && expr
+            .linear_combinations
+            .iter()
+            .all(|(coefficient, _)| fits_in_goldilocks_field(coefficient))
+}
@@ -0,0 +1,287 @@
+use super::*;
+use crate::circuit_translation::bn254_arithmetic::{
+    biguint_to_limbs, field_element_to_biguint, Bn254Arithmetic, LIMB_BITS,
+    MAX_TERMS_PER_EXPRESSION, NUM_LIMBS,
+};
+use crate::circuit_translation::targets::Bn254Target;
+
+// Translates ACIR keeping every witness as a BN254 scalar, so field arithmetic
+// (inversions, negative numbers, wrap-arounds) agrees with the ACVM execution
+pub struct CircuitBuilderFromAcirToEmulatedBn254 {
+    pub builder: CB,
+    pub witness_target_map: HashMap<Witness, Bn254Target>,
+    // Return values in the order the ABI lists them, when it does
+    pub return_witnesses: Option<Vec<Witness>>,
+}
+
+pub fn bn254_limbs_of(value: &FieldElement) -> Vec<F> {
+    biguint_to_limbs(&field_element_to_biguint(value), NUM_LIMBS)
+}
+
+// What keeps a program from being translated in emulated BN254 mode
+#[derive(Clone, PartialEq, Debug)]
+pub enum UnsupportedInEmulatedBn254Mode {
+    Opcode(String),
+    MultipleFunctions,
+}
+
+impl std::fmt::Display for UnsupportedInEmulatedBn254Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnsupportedInEmulatedBn254Mode::Opcode(opcode) => write!(
+                f,
+                "The {} opcode is unsupported in --emulate-bn254 mode, which only supports arithmetic, range, AND and XOR opcodes",
+                opcode
+            ),
+            UnsupportedInEmulatedBn254Mode::MultipleFunctions => write!(
+                f,
+                "Programs with more than one function are unsupported in --emulate-bn254 mode"
+            ),
+        }
+    }
+}
+
+// Checked before building the circuit or solving the witness, so write_vk and prove fail early
+// naming the first opcode the emulated mode cannot translate
+pub fn check_emulated_bn254_support(program: &Program) {
+    if let Err(unsupported) = emulated_bn254_support(program) {
+        panic!("{}", unsupported);
+    }
+}
+
+pub fn emulated_bn254_support(program: &Program) -> Result<(), UnsupportedInEmulatedBn254Mode> {
+    for opcode in &program.functions[0].opcodes {
+        let unsupported_opcode = match opcode {
+            Opcode::AssertZero(_) | Opcode::BrilligCall { .. } => None,
+            Opcode::BlackBoxFuncCall(
+                opcodes::BlackBoxFuncCall::RANGE { .. }
+                | opcodes::BlackBoxFuncCall::AND { .. }
+                | opcodes::BlackBoxFuncCall::XOR { .. },
+            ) => None,
+            Opcode::BlackBoxFuncCall(func_call) => {
+                Some(format!("{:?}", func_call.get_black_box_func()))
+            }
+            Opcode::MemoryInit { .. } => Some("MemoryInit".to_string()),
+            Opcode::MemoryOp { .. } => Some("MemoryOp".to_string()),
+            Opcode::Call { .. } => Some("Call".to_string()),
+        };
+        if let Some(unsupported_opcode) = unsupported_opcode {
+            return Err(UnsupportedInEmulatedBn254Mode::Opcode(unsupported_opcode));
+        }
+    }
+    if program.functions.len() > 1 {
+        return Err(UnsupportedInEmulatedBn254Mode::MultipleFunctions);
+    }
+    Ok(())
+}
+
+impl CircuitBuilderFromAcirToEmulatedBn254 {
+    pub fn new() -> Self {
+        Self::new_with_config(CircuitConfig::standard_recursion_config())
+    }
+
+    pub fn new_with_config(config: CircuitConfig) -> Self {
+        let builder = CB::new(config);
+        let witness_target_map: HashMap<Witness, Bn254Target> = HashMap::new();
+        Self {
+            builder,
+            witness_target_map,
+            return_witnesses: None,
+        }
+    }
+
+    pub fn unpack<C: GenericConfig<D, F = F>>(
+        self,
+    ) -> (CircuitData<F, C, 2>, HashMap<Witness, Bn254Target>) {
+        (self.builder.build::<C>(), self.witness_target_map)
+    }
+
+    pub fn translate_circuit(self: &mut Self, circuit: &Circuit) {
+        self._register_public_parameters_from_acir_circuit(circuit);
+        self._register_return_values_from_acir_circuit(circuit);
+        for opcode in &circuit.opcodes {
+            match opcode {
+                Opcode::AssertZero(expr) => {
+                    self._translate_assert_zero(expr);
+                }
+                Opcode::BrilligCall {
+                    id: _,
+                    inputs: _,
+                    outputs: _,
+                    predicate: _,
+                } => {}
+                Opcode::BlackBoxFuncCall(func_call) => {
+                    match func_call {
+                        opcodes::BlackBoxFuncCall::RANGE { input } => {
+                            self._range_check(input);
+                        }
+                        opcodes::BlackBoxFuncCall::AND { lhs, rhs, output } => {
+                            self._extend_circuit_with_bitwise_operation(
+                                lhs,
+                                rhs,
+                                output,
+                                Self::and,
+                            );
+                        }
+                        opcodes::BlackBoxFuncCall::XOR { lhs, rhs, output } => {
+                            self._extend_circuit_with_bitwise_operation(
+                                lhs,
+                                rhs,
+                                output,
+                                Self::xor,
+                            );
+                        }
+                        blackbox_func => {
+                            panic!(
+                                "Blackbox func not supported yet in emulated BN254 mode: {:?}",
+                                blackbox_func
+                            );
+                        }
+                    };
+                }
+                opcode => {
+                    panic!(
+                        "Opcode not supported yet in emulated BN254 mode: {:?}",
+                        opcode
+                    );
+                }
+            }
+        }
+    }
+
+    fn _translate_assert_zero(self: &mut Self, expr: &Expression) {
+        assert!(
+            expr.mul_terms.len() + expr.linear_combinations.len() <= MAX_TERMS_PER_EXPRESSION,
+            "Expression has too many terms to be emulated"
+        );
+        let mul_terms: Vec<(FieldElement, Bn254Target, Bn254Target)> = expr
+            .mul_terms
+            .iter()
+            .map(|(coefficient, lhs, rhs)| {
+                let lhs_target = self._get_or_create_target_for_witness(*lhs);
+                let rhs_target = self._get_or_create_target_for_witness(*rhs);
+                (*coefficient, lhs_target, rhs_target)
+            })
+            .collect();
+        let linear_terms: Vec<(FieldElement, Bn254Target)> = expr
+            .linear_combinations
+            .iter()
+            .map(|(coefficient, witness)| {
+                (
+                    *coefficient,
+                    self._get_or_create_target_for_witness(*witness),
+                )
+            })
+            .collect();
+
+        let mut arithmetic = Bn254Arithmetic::new_for(&mut self.builder);
+        let mut columns = arithmetic.zero_columns();
+        for (coefficient, lhs, rhs) in &mul_terms {
+            let product = arithmetic.mul(lhs, rhs);
+            arithmetic.add_scaled(&mut columns, coefficient, &product);
+        }
+        for (coefficient, target) in &linear_terms {
+            arithmetic.add_scaled(&mut columns, coefficient, target);
+        }
+        arithmetic.add_constant(&mut columns, &expr.q_c);
+        arithmetic.assert_zero_mod_p(columns);
+    }
+
+    // Limbs are already 16 bits wide, so only the limb holding the bound needs a check
+    fn _range_check(self: &mut Self, input: &FunctionInput) {
+        let target = self._get_or_create_target_for_witness(input.witness);
+        let num_bits = input.num_bits as usize;
+        for (position, limb) in target.limbs.iter().enumerate() {
+            let limb_start = position * LIMB_BITS;
+            if limb_start >= num_bits {
+                self.builder.assert_zero(*limb);
+            } else if num_bits - limb_start < LIMB_BITS {
+                self.builder.range_check(*limb, num_bits - limb_start);
+            }
+        }
+    }
+
+    fn _extend_circuit_with_bitwise_operation(
+        self: &mut Self,
+        lhs: &FunctionInput,
+        rhs: &FunctionInput,
+        output: &Witness,
+        operation: fn(&mut Self, BoolTarget, BoolTarget) -> BoolTarget,
+    ) {
+        assert_eq!(lhs.num_bits, rhs.num_bits);
+        self._range_check(lhs);
+        self._range_check(rhs);
+        let lhs_target = self._get_or_create_target_for_witness(lhs.witness);
+        let rhs_target = self._get_or_create_target_for_witness(rhs.witness);
+        let output_target = self._get_or_create_target_for_witness(*output);
+
+        let num_bits = lhs.num_bits as usize;
+        for position in 0..NUM_LIMBS {
+            let limb_start = position * LIMB_BITS;
+            if limb_start >= num_bits {
+                self.builder.assert_zero(output_target.limbs[position]);
+                continue;
+            }
+            let limb_bits = (num_bits - limb_start).min(LIMB_BITS);
+            let lhs_bits = self.builder.split_le(lhs_target.limbs[position], limb_bits);
+            let rhs_bits = self.builder.split_le(rhs_target.limbs[position], limb_bits);
+            let output_bits: Vec<BoolTarget> = lhs_bits
+                .into_iter()
+                .zip(rhs_bits.into_iter())
+                .map(|(x, y)| operation(self, x, y))
+                .collect();
+            let output_limb = self.builder.le_sum(output_bits.into_iter());
+            self.builder
+                .connect(output_target.limbs[position], output_limb);
+        }
+    }
+
+    fn _register_public_parameters_from_acir_circuit(self: &mut Self, circuit: &Circuit) {
+        let public_parameters_as_list: Vec<Witness> =
+            circuit.public_parameters.0.iter().cloned().collect();
+        for public_parameter_witness in public_parameters_as_list {
+            self._register_new_public_input_from_witness(public_parameter_witness);
+        }
+    }
+
+    fn _register_return_values_from_acir_circuit(self: &mut Self, circuit: &Circuit) {
+        for return_value_witness in return_value_witnesses(circuit, &self.return_witnesses) {
+            self._register_new_public_input_from_witness(return_value_witness);
+        }
+    }
+
+    // Public inputs must be canonical, otherwise x and x + p would both be accepted
+    fn _register_new_public_input_from_witness(
+        self: &mut Self,
+        public_input_witness: Witness,
+    ) -> Bn254Target {
+        let public_input_target = self._get_or_create_target_for_witness(public_input_witness);
+        self.builder
+            .register_public_inputs(&public_input_target.limbs);
+        Bn254Arithmetic::new_for(&mut self.builder).assert_canonical(&public_input_target);
+        public_input_target
+    }
+
+    fn _get_or_create_target_for_witness(self: &mut Self, witness: Witness) -> Bn254Target {
+        match self.witness_target_map.get(&witness) {
+            Some(target) => target.clone(),
+            None => {
+                let target = Bn254Arithmetic::new_for(&mut self.builder).add_virtual_bn254_target();
+                self.witness_target_map.insert(witness, target.clone());
+                target
+            }
+        }
+    }
+
+    fn and(&mut self, b1: BoolTarget, b2: BoolTarget) -> BoolTarget {
+        self.builder.and(b1, b2)
+    }
+
+    fn xor(&mut self, b1: BoolTarget, b2: BoolTarget) -> BoolTarget {
+        // a xor b = (a or b) and (not (a and b))
+        let b1_or_b2 = self.builder.or(b1, b2);
+        let b1_and_b2 = self.builder.and(b1, b2);
+        let not_b1_and_b2 = self.builder.not(b1_and_b2);
+        self.builder.and(b1_or_b2, not_b1_and_b2)
+    }
+}
@@ -0,0 +1,73 @@
+use super::*;
+use crate::circuit_translation::assert_zero_generators::AssertZeroSolverGenerator;
+use crate::circuit_translation::bn254_generators::{
+    Bn254CarriesGenerator, Bn254ComplementGenerator, Bn254ReductionGenerator,
+};
+use crate::circuit_translation::memory_generators::{
+    MemoryStateGenerator, SortedMemoryTraceGenerator,
+};
+use plonky2::gadgets::arithmetic::EqualityGenerator;
+use plonky2::gadgets::arithmetic_extension::QuotientGeneratorExtension;
+use plonky2::gadgets::range_check::LowHighGenerator;
+use plonky2::gadgets::split_base::BaseSumGenerator;
+use plonky2::gadgets::split_join::{SplitGenerator, WireSplitGenerator};
+use plonky2::gates::arithmetic_base::ArithmeticBaseGenerator;
+use plonky2::gates::arithmetic_extension::ArithmeticExtensionGenerator;
+use plonky2::gates::base_sum::BaseSplitGenerator;
+use plonky2::gates::coset_interpolation::InterpolationGenerator;
+use plonky2::gates::exponentiation::ExponentiationGenerator;
+use plonky2::gates::lookup::LookupGenerator;
+use plonky2::gates::lookup_table::LookupTableGenerator;
+use plonky2::gates::multiplication_extension::MulExtensionGenerator;
+use plonky2::gates::poseidon::PoseidonGenerator;
+use plonky2::gates::poseidon_mds::PoseidonMdsGenerator;
+use plonky2::gates::random_access::RandomAccessGenerator;
+use plonky2::gates::reducing::ReducingGenerator;
+use plonky2::gates::reducing_extension::ReducingGenerator as ReducingExtensionGenerator;
+use plonky2::iop::generator::{
+    ConstantGenerator, CopyGenerator, NonzeroTestGenerator, RandomValueGenerator,
+};
+use plonky2::plonk::config::PoseidonGoldilocksConfig;
+use plonky2::recursion::dummy_circuit::DummyProofGenerator;
+use plonky2::util::serialization::WitnessGeneratorSerializer;
+use plonky2::{get_generator_tag_impl, impl_generator_serializer, read_generator_impl};
+
+// Plonky2's default generators plus the ones added by the translators, so a whole
+// CircuitData can be written to a file and proven from later
+pub struct BackendGeneratorSerializer;
+
+impl WitnessGeneratorSerializer<F, D> for BackendGeneratorSerializer {
+    impl_generator_serializer! {
+        BackendGeneratorSerializer,
+        ArithmeticBaseGenerator<F, D>,
+        ArithmeticExtensionGenerator<F, D>,
+        BaseSplitGenerator<2>,
+        BaseSumGenerator<2>,
+        ConstantGenerator<F>,
+        CopyGenerator,
+        DummyProofGenerator<F, PoseidonGoldilocksConfig, D>,
+        EqualityGenerator,
+        ExponentiationGenerator<F, D>,
+        InterpolationGenerator<F, D>,
+        LookupGenerator,
+        LookupTableGenerator,
+        LowHighGenerator,
+        MulExtensionGenerator<F, D>,
+        NonzeroTestGenerator,
+        PoseidonGenerator<F, D>,
+        PoseidonMdsGenerator<D>,
+        QuotientGeneratorExtension<D>,
+        RandomAccessGenerator<F, D>,
+        RandomValueGenerator,
+        ReducingGenerator<D>,
+        ReducingExtensionGenerator<D>,
+        SplitGenerator,
+        WireSplitGenerator,
+        AssertZeroSolverGenerator,
+        SortedMemoryTraceGenerator,
+        MemoryStateGenerator,
+        Bn254ReductionGenerator,
+        Bn254CarriesGenerator,
+        Bn254ComplementGenerator
+    }
+}
@@ -0,0 +1,164 @@
+use super::*;
+use crate::circuit_translation::targets::MemoryAccessTarget;
+use plonky2::field::types::PrimeField64;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
+use plonky2::iop::witness::{PartitionWitness, Witness as WitnessRead, WitnessWrite};
+use plonky2::plonk::circuit_data::CommonCircuitData;
+use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
+
+#[derive(Debug, Clone, Default)]
+pub struct SortedMemoryTraceGenerator {
+    pub trace: Vec<MemoryAccessTarget>,
+    pub sorted_trace: Vec<MemoryAccessTarget>,
+}
+
+impl SimpleGenerator<F, D> for SortedMemoryTraceGenerator {
+    fn id(&self) -> String {
+        "SortedMemoryTraceGenerator".to_string()
+    }
+
+    fn dependencies(&self) -> Vec<Target> {
+        _flatten_accesses(&self.trace)
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let mut accesses: Vec<Vec<F>> = self
+            .trace
+            .iter()
+            .map(|access| witness.get_targets(&access.targets()))
+            .collect();
+        accesses.sort_by_key(|access| (access[0].to_canonical_u64(), access[1].to_canonical_u64()));
+        for (sorted_access, values) in self.sorted_trace.iter().zip(accesses) {
+            out_buffer.set_target_arr(&sorted_access.targets(), &values);
+        }
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_vec(&_flatten_accesses(&self.trace))?;
+        dst.write_target_vec(&_flatten_accesses(&self.sorted_trace))
+    }
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let trace = _unflatten_accesses(&src.read_target_vec()?);
+        let sorted_trace = _unflatten_accesses(&src.read_target_vec()?);
+        Ok(Self {
+            trace,
+            sorted_trace,
+        })
+    }
+}
+
+// An access of a memory block as seen by the MemoryStateGenerator
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemoryStateAccess {
+    pub index: Target,
+    pub is_write: Target,
+    // Reads have no value to write, their value comes from the memory
+    pub written_value: Option<Target>,
+    // Writes that always happen do not need the value they overwrite
+    pub current_value: Option<Target>,
+}
+
+// Replays every access of a memory block once, in program order, setting the value stored at
+// each accessed index right before the access. Read values also come from the witness, so an
+// index computed from a read of the same block, as in x[x[0]], does not wait on this generator
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStateGenerator {
+    pub initial_values: Vec<Target>,
+    pub accesses: Vec<MemoryStateAccess>,
+}
+
+impl SimpleGenerator<F, D> for MemoryStateGenerator {
+    fn id(&self) -> String {
+        "MemoryStateGenerator".to_string()
+    }
+
+    fn dependencies(&self) -> Vec<Target> {
+        let mut dependencies = self.initial_values.clone();
+        for access in &self.accesses {
+            dependencies.push(access.index);
+            dependencies.push(access.is_write);
+            dependencies.extend(access.written_value);
+        }
+        dependencies
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let mut memory: Vec<F> = witness.get_targets(&self.initial_values);
+        for access in &self.accesses {
+            let index = witness.get_target(access.index).to_canonical_u64() as usize;
+            let current_value = memory.get(index).copied().unwrap_or(F::ZERO);
+            if let Some(current_value_target) = access.current_value {
+                out_buffer.set_target(current_value_target, current_value);
+            }
+            // Out of bounds writes are rejected by the circuit, so they are just skipped here
+            if let (Some(written_value), Some(stored_value)) =
+                (access.written_value, memory.get_mut(index))
+            {
+                if witness.get_target(access.is_write).is_one() {
+                    *stored_value = witness.get_target(written_value);
+                }
+            }
+        }
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_vec(&self.initial_values)?;
+        dst.write_usize(self.accesses.len())?;
+        for access in &self.accesses {
+            dst.write_target(access.index)?;
+            dst.write_target(access.is_write)?;
+            _write_optional_target(dst, access.written_value)?;
+            _write_optional_target(dst, access.current_value)?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let initial_values = src.read_target_vec()?;
+        let amount_of_accesses = src.read_usize()?;
+        let accesses = (0..amount_of_accesses)
+            .map(|_| {
+                Ok(MemoryStateAccess {
+                    index: src.read_target()?,
+                    is_write: src.read_target()?,
+                    written_value: _read_optional_target(src)?,
+                    current_value: _read_optional_target(src)?,
+                })
+            })
+            .collect::<IoResult<Vec<MemoryStateAccess>>>()?;
+        Ok(Self {
+            initial_values,
+            accesses,
+        })
+    }
+}
+
+fn _write_optional_target(dst: &mut Vec<u8>, target: Option<Target>) -> IoResult<()> {
+    dst.write_bool(target.is_some())?;
+    match target {
+        Some(target) => dst.write_target(target),
+        None => Ok(()),
+    }
+}
+
+fn _read_optional_target(src: &mut Buffer) -> IoResult<Option<Target>> {
+    match src.read_bool()? {
+        true => Ok(Some(src.read_target()?)),
+        false => Ok(None),
+    }
+}
+
+fn _flatten_accesses(accesses: &Vec<MemoryAccessTarget>) -> Vec<Target> {
+    accesses
+        .iter()
+        .flat_map(|access| access.targets())
+        .collect()
+}
+
+fn _unflatten_accesses(targets: &Vec<Target>) -> Vec<MemoryAccessTarget> {
+    targets
+        .chunks(MemoryAccessTarget::NUMBER_OF_TARGETS)
+        .map(MemoryAccessTarget::from_targets)
+        .collect()
+}
@@ -0,0 +1,378 @@
+use super::*;
+use crate::circuit_translation::memory_generators::{
+    MemoryStateAccess, MemoryStateGenerator, SortedMemoryTraceGenerator,
+};
+use crate::circuit_translation::targets::MemoryAccessTarget;
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::iop::ext_target::ExtensionTarget;
+use plonky2::util::log2_ceil;
+
+// A random access gate over 2^7 values does not fit in the standard recursion config
+const MAX_RANDOM_ACCESS_BITS: usize = 6;
+const MAX_TIMESTAMP_BITS: usize = 32;
+
+#[derive(Clone, Copy)]
+pub enum AccessKind {
+    Read,
+    Write,
+    // The operation is an expression that is only known when proving
+    Dynamic(BoolTarget),
+}
+
+pub struct MemoryOperation {
+    pub index: Target,
+    pub value: Target,
+    pub access_kind: AccessKind,
+    pub predicate: Option<BoolTarget>,
+}
+
+pub struct MemoryBlock {
+    pub init: Vec<Target>,
+    pub operations: Vec<MemoryOperation>,
+}
+
+impl MemoryBlock {
+    pub fn new_with_initial_values(init: Vec<Target>) -> Self {
+        Self {
+            init,
+            operations: Vec::new(),
+        }
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.operations
+            .iter()
+            .all(|operation| matches!(operation.access_kind, AccessKind::Read))
+    }
+}
+
+pub struct MemoryTranslator<'a> {
+    builder: &'a mut CircuitBuilder<F, D>,
+    memory_block: &'a MemoryBlock,
+}
+
+impl<'a> MemoryTranslator<'a> {
+    pub fn new_for(
+        builder: &'a mut CircuitBuilder<F, D>,
+        memory_block: &'a MemoryBlock,
+    ) -> MemoryTranslator<'a> {
+        Self {
+            builder,
+            memory_block,
+        }
+    }
+
+    pub fn translate(&mut self) {
+        // Noir can create empty arrays, which are fine as long as nothing accesses them
+        if self.memory_block.init.is_empty() {
+            assert!(
+                self.memory_block.operations.is_empty(),
+                "Cannot access an empty memory block"
+            );
+            return;
+        }
+        if self.memory_block.is_read_only() {
+            self._translate_read_only_memory();
+        } else {
+            self._translate_read_write_memory();
+        }
+    }
+
+    fn _translate_read_only_memory(self: &mut Self) {
+        let memory_block = self.memory_block;
+        for operation in &memory_block.operations {
+            let index = self._enabled_index(operation);
+            let read_value = self._random_access(index, &memory_block.init);
+            self._connect_read_value(operation, read_value);
+        }
+    }
+
+    // Disabled operations access the index 0, which is always within bounds
+    fn _enabled_index(self: &mut Self, operation: &MemoryOperation) -> Target {
+        match operation.predicate {
+            Some(predicate) => self.builder.mul(predicate.target, operation.index),
+            None => operation.index,
+        }
+    }
+
+    // Disabled reads return 0, as in the ACVM
+    fn _connect_read_value(self: &mut Self, operation: &MemoryOperation, read_value: Target) {
+        match operation.predicate {
+            Some(predicate) => {
+                let enabled_read_value = self.builder.mul(predicate.target, read_value);
+                self.builder.connect(operation.value, enabled_read_value);
+            }
+            None => self.builder.connect(operation.value, read_value),
+        }
+    }
+
+    fn _random_access(self: &mut Self, index: Target, values: &Vec<Target>) -> Target {
+        assert!(!values.is_empty(), "Cannot access an empty memory block");
+        let index_bits = log2_ceil(values.len());
+        if index_bits == 0 {
+            self.builder.assert_zero(index);
+            return values[0];
+        }
+
+        let index_as_bits = self.builder.split_le(index, index_bits);
+        self._assert_index_is_lower_than(index, values.len(), index_bits);
+
+        let zero = self.builder.zero();
+        let mut padded_values = values.clone();
+        padded_values.resize(1 << index_bits, zero);
+        self._random_access_from_bits(&index_as_bits, padded_values)
+    }
+
+    fn _assert_index_is_lower_than(
+        self: &mut Self,
+        index: Target,
+        length: usize,
+        index_bits: usize,
+    ) {
+        let last_index = self.builder.constant(F::from_canonical_usize(length - 1));
+        let distance_to_last_index = self.builder.sub(last_index, index);
+        self.builder.range_check(distance_to_last_index, index_bits);
+    }
+
+    fn _random_access_from_bits(
+        self: &mut Self,
+        index_as_bits: &[BoolTarget],
+        values: Vec<Target>,
+    ) -> Target {
+        if index_as_bits.len() <= MAX_RANDOM_ACCESS_BITS {
+            let index = self.builder.le_sum(index_as_bits.iter());
+            return self.builder.random_access(index, values);
+        }
+        let (low_bits, high_bits) = index_as_bits.split_at(MAX_RANDOM_ACCESS_BITS);
+        let values_per_chunk = 1 << MAX_RANDOM_ACCESS_BITS;
+        let chunk_values: Vec<Target> = values
+            .chunks(values_per_chunk)
+            .map(|chunk| self._random_access_from_bits(low_bits, chunk.to_vec()))
+            .collect();
+        self._random_access_from_bits(high_bits, chunk_values)
+    }
+
+    // Memory checking over a trace of (index, timestamp, value, is_write) accesses: the
+    // initial values are writes with the lowest timestamps, and the trace sorted by
+    // (index, timestamp) must be a permutation of the program-ordered one in which every
+    // read sees the value of the previous access to the same index.
+    fn _translate_read_write_memory(self: &mut Self) {
+        let trace = self._memory_trace();
+        let sorted_trace: Vec<MemoryAccessTarget> = (0..trace.len())
+            .map(|_| self._new_memory_access_target())
+            .collect();
+        self.builder
+            .add_simple_generator(SortedMemoryTraceGenerator {
+                trace: trace.clone(),
+                sorted_trace: sorted_trace.clone(),
+            });
+
+        self._assert_sorted_trace_is_consistent(&sorted_trace);
+        self._assert_is_permutation(&trace, &sorted_trace);
+    }
+
+    fn _memory_trace(self: &mut Self) -> Vec<MemoryAccessTarget> {
+        let memory_block = self.memory_block;
+        let mut trace = Vec::new();
+        for (position, initial_value) in memory_block.init.iter().enumerate() {
+            let index = self.builder.constant(F::from_canonical_usize(position));
+            let is_write = self.builder.one();
+            trace.push(MemoryAccessTarget {
+                index,
+                timestamp: index,
+                value: *initial_value,
+                is_write,
+            });
+        }
+        let mut state_accesses = Vec::new();
+        for (position, operation) in memory_block.operations.iter().enumerate() {
+            let timestamp_as_usize = memory_block.init.len() + position;
+            let timestamp = self
+                .builder
+                .constant(F::from_canonical_usize(timestamp_as_usize));
+            let index = self._enabled_index(operation);
+            let is_write = self._enabled_is_write(operation);
+            let (value, state_access) = self._accessed_value(operation, index, is_write);
+            state_accesses.push(state_access);
+            trace.push(MemoryAccessTarget {
+                index,
+                timestamp,
+                value,
+                is_write,
+            });
+        }
+        self.builder.add_simple_generator(MemoryStateGenerator {
+            initial_values: memory_block.init.clone(),
+            accesses: state_accesses,
+        });
+        trace
+    }
+
+    // Disabled writes are recorded as reads, so they leave the memory unchanged
+    fn _enabled_is_write(self: &mut Self, operation: &MemoryOperation) -> Target {
+        match (operation.access_kind, operation.predicate) {
+            (AccessKind::Read, _) => self.builder.zero(),
+            (AccessKind::Write, Some(predicate)) => predicate.target,
+            (AccessKind::Write, None) => self.builder.one(),
+            (AccessKind::Dynamic(is_write), Some(predicate)) => {
+                self.builder.and(is_write, predicate).target
+            }
+            (AccessKind::Dynamic(is_write), None) => is_write.target,
+        }
+    }
+
+    // Returns the value in memory after the access, and what the MemoryStateGenerator needs to
+    // know the value before it
+    fn _accessed_value(
+        self: &mut Self,
+        operation: &MemoryOperation,
+        index: Target,
+        is_write: Target,
+    ) -> (Target, MemoryStateAccess) {
+        let mut state_access = MemoryStateAccess {
+            index,
+            is_write,
+            written_value: match operation.access_kind {
+                AccessKind::Read => None,
+                _ => Some(operation.value),
+            },
+            current_value: None,
+        };
+        if let (AccessKind::Write, None) = (operation.access_kind, operation.predicate) {
+            return (operation.value, state_access);
+        }
+
+        let current_value = self.builder.add_virtual_target();
+        state_access.current_value = Some(current_value);
+        let value = match operation.access_kind {
+            AccessKind::Read => {
+                self._connect_read_value(operation, current_value);
+                current_value
+            }
+            AccessKind::Write => self.builder.select(
+                BoolTarget::new_unsafe(is_write),
+                operation.value,
+                current_value,
+            ),
+            AccessKind::Dynamic(is_write_operation) => {
+                self._assert_read_value_when_not_writing(
+                    operation,
+                    is_write_operation,
+                    current_value,
+                );
+                self.builder.select(
+                    BoolTarget::new_unsafe(is_write),
+                    operation.value,
+                    current_value,
+                )
+            }
+        };
+        (value, state_access)
+    }
+
+    fn _assert_read_value_when_not_writing(
+        self: &mut Self,
+        operation: &MemoryOperation,
+        is_write_operation: BoolTarget,
+        current_value: Target,
+    ) {
+        let read_value = match operation.predicate {
+            Some(predicate) => self.builder.mul(predicate.target, current_value),
+            None => current_value,
+        };
+        let is_read_operation = self.builder.not(is_write_operation);
+        let read_value_difference = self.builder.sub(operation.value, read_value);
+        let read_value_difference_when_reading = self
+            .builder
+            .mul(is_read_operation.target, read_value_difference);
+        self.builder.assert_zero(read_value_difference_when_reading);
+    }
+
+    fn _new_memory_access_target(self: &mut Self) -> MemoryAccessTarget {
+        MemoryAccessTarget {
+            index: self.builder.add_virtual_target(),
+            timestamp: self.builder.add_virtual_target(),
+            value: self.builder.add_virtual_target(),
+            is_write: self.builder.add_virtual_target(),
+        }
+    }
+
+    fn _assert_sorted_trace_is_consistent(self: &mut Self, sorted_trace: &Vec<MemoryAccessTarget>) {
+        // Together with index steps of 0 or 1, this restricts every index to the block bounds
+        let zero = self.builder.zero();
+        let last_index = self
+            .builder
+            .constant(F::from_canonical_usize(self.memory_block.init.len() - 1));
+        self.builder.connect(sorted_trace[0].index, zero);
+        self.builder
+            .connect(sorted_trace[sorted_trace.len() - 1].index, last_index);
+
+        for consecutive_accesses in sorted_trace.windows(2) {
+            let previous = &consecutive_accesses[0];
+            let current = &consecutive_accesses[1];
+
+            let index_step = self.builder.sub(current.index, previous.index);
+            let index_step = BoolTarget::new_unsafe(index_step);
+            self.builder.assert_bool(index_step);
+            let is_same_index = self.builder.not(index_step);
+
+            let timestamp_step = self.builder.sub(current.timestamp, previous.timestamp);
+            let one = self.builder.one();
+            let timestamp_gap = self.builder.sub(timestamp_step, one);
+            let timestamp_gap_for_same_index =
+                self.builder.mul(is_same_index.target, timestamp_gap);
+            self.builder
+                .range_check(timestamp_gap_for_same_index, MAX_TIMESTAMP_BITS);
+
+            let is_read = self.builder.not(BoolTarget::new_unsafe(current.is_write));
+            let is_read_of_same_index = self.builder.and(is_same_index, is_read);
+            let value_step = self.builder.sub(current.value, previous.value);
+            let value_step_for_read = self.builder.mul(is_read_of_same_index.target, value_step);
+            self.builder.assert_zero(value_step_for_read);
+        }
+    }
+
+    fn _assert_is_permutation(
+        self: &mut Self,
+        trace: &Vec<MemoryAccessTarget>,
+        sorted_trace: &Vec<MemoryAccessTarget>,
+    ) {
+        let transcript: Vec<Target> = trace
+            .iter()
+            .chain(sorted_trace.iter())
+            .flat_map(|access| access.targets())
+            .collect();
+        let challenges = self
+            .builder
+            .hash_n_to_hash_no_pad::<PoseidonHash>(transcript)
+            .elements;
+        let alpha = ExtensionTarget([challenges[0], challenges[1]]);
+        let beta = ExtensionTarget([challenges[2], challenges[3]]);
+
+        let trace_fingerprint = self._fingerprint(trace, alpha, beta);
+        let sorted_trace_fingerprint = self._fingerprint(sorted_trace, alpha, beta);
+        self.builder
+            .connect_extension(trace_fingerprint, sorted_trace_fingerprint);
+    }
+
+    fn _fingerprint(
+        self: &mut Self,
+        accesses: &Vec<MemoryAccessTarget>,
+        alpha: ExtensionTarget<D>,
+        beta: ExtensionTarget<D>,
+    ) -> ExtensionTarget<D> {
+        let mut fingerprint = self.builder.one_extension();
+        for access in accesses {
+            let mut compressed_access = self.builder.zero_extension();
+            for target in access.targets().into_iter().rev() {
+                let target_as_extension = self.builder.convert_to_ext(target);
+                compressed_access =
+                    self.builder
+                        .mul_add_extension(compressed_access, beta, target_as_extension);
+            }
+            let factor = self.builder.sub_extension(alpha, compressed_access);
+            fingerprint = self.builder.mul_extension(fingerprint, factor);
+        }
+        fingerprint
+    }
+}
@@ -1,4 +1,6 @@
+use acir::circuit::brillig::BrilligOutputs;
 use acir::circuit::opcodes;
+use acir::circuit::opcodes::BlockId;
 use acir::circuit::opcodes::FunctionInput;
 use acir::circuit::opcodes::MemOp as GenericMemOp;
 use acir::circuit::Circuit as GenericCircuit;
@@ -6,31 +8,47 @@ use acir::circuit::Opcode as GenericOpcode;
 use acir::circuit::Program as GenericProgram;
 use acir::native_types::Expression as GenericExpression;
 pub use acir::native_types::Witness;
+use acir::native_types::WitnessMap as GenericWitnessMap;
 use acir::native_types::WitnessStack as GenericWitnessStack;
 use num_bigint::BigUint;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
+use std::collections::HashSet;
 // Generics
 pub use acir_field::AcirField;
 pub use acir_field::FieldElement;
+use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2::field::types::Field;
 use plonky2::iop::target::{BoolTarget, Target};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
 use plonky2::plonk::circuit_data::CircuitConfig;
 use plonky2::plonk::circuit_data::CircuitData;
-use plonky2::plonk::config::{GenericConfig, KeccakGoldilocksConfig};
+use plonky2::plonk::circuit_data::CommonCircuitData;
+use plonky2::plonk::config::GenericConfig;
 
+use crate::circuit_translation::memory_translator::{AccessKind, MemoryBlock, MemoryOperation};
 use crate::circuit_translation::targets::BinaryDigitsTarget;
 
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
 
+pub mod assert_zero_generators;
 pub mod assert_zero_translator;
-mod targets;
+pub mod bn254_arithmetic;
+pub mod bn254_generators;
+pub mod constant_analysis;
+pub mod emulated_bn254_translator;
+pub mod generator_serializer;
+pub mod memory_generators;
+pub mod memory_translator;
+pub mod recursive_aggregation_translator;
+pub mod sha256_translator;
+pub mod targets;
 
 const D: usize = 2;
 
-type C = KeccakGoldilocksConfig;
-type F = <C as GenericConfig<D>>::F;
+type F = GoldilocksField;
 type CB = CircuitBuilder<F, D>;
 
 // pub type FieldElement = GenericFieldElement<GoldilocksFr>;
@@ -40,38 +58,140 @@ pub type Program = GenericProgram<FieldElement>;
 pub type Expression = GenericExpression<FieldElement>;
 pub type MemOp = GenericMemOp<FieldElement>;
 pub type WitnessStack = GenericWitnessStack<FieldElement>;
+pub type WitnessMap = GenericWitnessMap<FieldElement>;
+
+// Every translator maps a witness to the same target, created the first time the witness is used
+pub fn get_or_create_target_for_witness(
+    builder: &mut CB,
+    witness_target_map: &mut HashMap<Witness, Target>,
+    witness: Witness,
+) -> Target {
+    *witness_target_map
+        .entry(witness)
+        .or_insert_with(|| builder.add_virtual_target())
+}
+
+// The ACIR keeps the return values as a set, so a tuple returned with its witnesses out of order
+// only gets its public inputs in the ABI order when the ABI lists the return witnesses
+pub fn return_value_witnesses(
+    circuit: &Circuit,
+    return_witnesses: &Option<Vec<Witness>>,
+) -> Vec<Witness> {
+    match return_witnesses {
+        Some(return_witnesses) => {
+            assert_eq!(
+                circuit.return_values.0,
+                return_witnesses
+                    .iter()
+                    .cloned()
+                    .collect::<BTreeSet<Witness>>(),
+                "The return witnesses of the ABI must be the return values of the program"
+            );
+            return_witnesses.clone()
+        }
+        None => circuit.return_values.0.iter().cloned().collect(),
+    }
+}
 
 pub struct CircuitBuilderFromAcirToPlonky2 {
     pub builder: CB,
     pub witness_target_map: HashMap<Witness, Target>,
+    pub memory_blocks: BTreeMap<u32, MemoryBlock>,
+    pub functions: Vec<Circuit>,
+    pub inlined_calls: Vec<InlinedCall>,
+    // Targets whose value is computed in-circuit instead of taken from the witness
+    pub computed_targets: HashSet<Target>,
+    // Common data of the programs whose proofs RecursiveAggregation opcodes verify, either one for
+    // all of them or one for each opcode in translation order
+    pub recursive_common_data: Vec<CommonCircuitData<F, D>>,
+    pub amount_of_recursive_aggregations: usize,
+    // Whether the call being inlined is enabled, when it or any enclosing call has a predicate
+    pub call_predicate: Option<BoolTarget>,
+    // Witnesses whose values are available to the ACVM at the opcode being translated
+    pub known_witnesses: HashSet<Witness>,
+    // Return values of main in the order the ABI lists them, when it does
+    pub return_witnesses: Option<Vec<Witness>>,
+}
+
+// Witnesses of a called function are local to each call, so every inlined call keeps its own map.
+// Calls are listed once they are inlined, so the calls nested in each one come right before it
+pub struct InlinedCall {
+    pub function_id: u32,
+    pub witness_target_map: HashMap<Witness, Target>,
+    // Evaluated over the witnesses of the caller, None when the call is always enabled
+    pub predicate: Option<Expression>,
+    pub amount_of_nested_calls: usize,
 }
 
 impl CircuitBuilderFromAcirToPlonky2 {
     pub fn new() -> Self {
-        let config = CircuitConfig::standard_recursion_config();
+        Self::new_with_config(CircuitConfig::standard_recursion_config())
+    }
+
+    pub fn new_with_config(config: CircuitConfig) -> Self {
         let builder = CB::new(config);
         let witness_target_map: HashMap<Witness, Target> = HashMap::new();
+        let memory_blocks: BTreeMap<u32, MemoryBlock> = BTreeMap::new();
         Self {
             builder,
             witness_target_map,
+            memory_blocks,
+            functions: Vec::new(),
+            inlined_calls: Vec::new(),
+            computed_targets: HashSet::new(),
+            recursive_common_data: Vec::new(),
+            amount_of_recursive_aggregations: 0,
+            call_predicate: None,
+            known_witnesses: HashSet::new(),
+            return_witnesses: None,
         }
     }
 
-    pub fn unpack(self) -> (CircuitData<F, C, 2>, HashMap<Witness, Target>) {
+    pub fn unpack<C: GenericConfig<D, F = F>>(
+        self,
+    ) -> (CircuitData<F, C, 2>, HashMap<Witness, Target>) {
         (self.builder.build::<C>(), self.witness_target_map)
     }
 
+    pub fn unpack_program<C: GenericConfig<D, F = F>>(
+        self,
+    ) -> (
+        CircuitData<F, C, 2>,
+        HashMap<Witness, Target>,
+        Vec<InlinedCall>,
+        HashSet<Target>,
+    ) {
+        (
+            self.builder.build::<C>(),
+            self.witness_target_map,
+            self.inlined_calls,
+            self.computed_targets,
+        )
+    }
+
+    // Every function called from main gets inlined in a single plonky2 circuit
+    pub fn translate_program(self: &mut Self, program: &Program) {
+        self.functions = program.functions.clone();
+        self.translate_circuit(&program.functions[0]);
+    }
+
     pub fn translate_circuit(self: &mut Self, circuit: &Circuit) {
+        self._assert_constants_fit_in_goldilocks_field(circuit);
+        self.known_witnesses
+            .extend(circuit.private_parameters.iter());
+        self.known_witnesses
+            .extend(circuit.public_parameters.0.iter());
         self._register_public_parameters_from_acir_circuit(circuit);
+        self._register_return_values_from_acir_circuit(circuit);
+        self._translate_opcodes(circuit);
+        self._translate_memory_blocks();
+    }
+
+    fn _translate_opcodes(self: &mut Self, circuit: &Circuit) {
         for opcode in &circuit.opcodes {
             match opcode {
                 Opcode::AssertZero(expr) => {
-                    let mut translator = assert_zero_translator::AssertZeroTranslator::new_for(
-                        &mut self.builder,
-                        &mut self.witness_target_map,
-                        &expr,
-                    );
-                    translator.translate();
+                    self._translate_assert_zero(expr);
                 }
                 Opcode::BrilligCall {
                     id: _,
@@ -80,27 +200,34 @@ impl CircuitBuilderFromAcirToPlonky2 {
                     predicate: _,
                 } => {}
                 Opcode::MemoryInit {
-                    block_id: _,
-                    init: _,
+                    block_id,
+                    init,
                     block_type: _,
-                } => {}
+                } => {
+                    self._register_memory_block(block_id, init);
+                }
                 Opcode::MemoryOp {
-                    block_id: _,
+                    block_id,
                     op,
-                    predicate: _,
+                    predicate,
                 } => {
-                    // TODO: check whether we should register if the predicate is false
-                    self._register_intermediate_witnesses_for_memory_op(&op);
+                    self._register_memory_op(block_id, &op, predicate);
+                }
+                Opcode::Call {
+                    id,
+                    inputs,
+                    outputs,
+                    predicate,
+                } => {
+                    self._translate_call(*id, inputs, outputs, predicate);
                 }
                 Opcode::BlackBoxFuncCall(func_call) => {
                     match func_call {
                         opcodes::BlackBoxFuncCall::RANGE { input } => {
                             let long_max_bits = input.num_bits.clone() as usize;
-                            assert!(long_max_bits <= 32,
-                                    "Range checks with more than 32 bits are not allowed yet while using Plonky2 prover");
                             let witness = input.witness;
                             let target = self._get_or_create_target_for_witness(witness);
-                            self.builder.range_check(target, long_max_bits)
+                            self._range_check(target, long_max_bits)
                         }
                         opcodes::BlackBoxFuncCall::AND { lhs, rhs, output } => {
                             self._extend_circuit_with_bitwise_operation(
@@ -121,6 +248,19 @@ impl CircuitBuilderFromAcirToPlonky2 {
                         opcodes::BlackBoxFuncCall::SHA256 { inputs, outputs } => {
                             self._extend_circuit_with_sha256_operation(inputs, outputs);
                         }
+                        opcodes::BlackBoxFuncCall::RecursiveAggregation {
+                            verification_key,
+                            proof,
+                            public_inputs,
+                            key_hash,
+                        } => {
+                            self._extend_circuit_with_recursive_aggregation(
+                                verification_key,
+                                proof,
+                                public_inputs,
+                                key_hash,
+                            );
+                        }
                         blackbox_func => {
                             panic!("Blackbox func not supported yet: {:?}", blackbox_func);
                         }
@@ -131,16 +271,228 @@ impl CircuitBuilderFromAcirToPlonky2 {
                     panic!("Opcode not supported yet: {:?}", opcode);
                 }
             }
+            self._mark_outputs_as_known(opcode);
+        }
+    }
+
+    fn _mark_outputs_as_known(self: &mut Self, opcode: &Opcode) {
+        let outputs: Vec<Witness> = match opcode {
+            Opcode::BrilligCall { outputs, .. } => outputs
+                .iter()
+                .flat_map(|output| match output {
+                    BrilligOutputs::Simple(witness) => vec![*witness],
+                    BrilligOutputs::Array(witnesses) => witnesses.clone(),
+                })
+                .collect(),
+            Opcode::MemoryOp { op, .. } => op.value.to_witness().into_iter().collect(),
+            Opcode::Call { outputs, .. } => outputs.clone(),
+            Opcode::BlackBoxFuncCall(func_call) => func_call.get_outputs_vec(),
+            _ => vec![],
+        };
+        self.known_witnesses.extend(outputs);
+    }
+
+    fn _translate_call(
+        self: &mut Self,
+        function_id: u32,
+        inputs: &Vec<Witness>,
+        outputs: &Vec<Witness>,
+        predicate: &Option<Expression>,
+    ) {
+        let output_targets: Vec<Target> = outputs
+            .iter()
+            .map(|witness| self._get_or_create_target_for_witness(*witness))
+            .collect();
+        let predicate = match predicate {
+            Some(predicate) if predicate.is_const() => {
+                if predicate.q_c.is_zero() {
+                    // The ACVM skips disabled calls and sets their outputs to zero
+                    for output_target in output_targets {
+                        self.builder.assert_zero(output_target);
+                    }
+                    return;
+                }
+                None
+            }
+            predicate => predicate.clone(),
+        };
+
+        let callee = self
+            .functions
+            .get(function_id as usize)
+            .expect("Called function must be part of the program")
+            .clone();
+        self._assert_constants_fit_in_goldilocks_field(&callee);
+        let enclosing_call_predicate = self.call_predicate;
+        self.call_predicate = self._and_call_predicate(&predicate);
+        // The callee sees the call inputs as its witnesses 0..n
+        let callee_witness_target_map: HashMap<Witness, Target> = inputs
+            .iter()
+            .enumerate()
+            .map(|(position, witness)| {
+                let input_target = self._get_or_create_target_for_witness(*witness);
+                (
+                    Witness(position as u32),
+                    self._gated_by_call_predicate(input_target),
+                )
+            })
+            .collect();
+
+        let caller_witness_target_map =
+            std::mem::replace(&mut self.witness_target_map, callee_witness_target_map);
+        let caller_known_witnesses = std::mem::replace(
+            &mut self.known_witnesses,
+            (0..inputs.len())
+                .map(|position| Witness(position as u32))
+                .collect(),
+        );
+        let caller_memory_blocks = std::mem::take(&mut self.memory_blocks);
+        let first_nested_call = self.inlined_calls.len();
+        self._translate_opcodes(&callee);
+        self._translate_memory_blocks();
+        let return_targets: Vec<Target> = callee
+            .return_values
+            .0
+            .iter()
+            .map(|witness| self._get_or_create_target_for_witness(*witness))
+            .collect();
+        let callee_witness_target_map =
+            std::mem::replace(&mut self.witness_target_map, caller_witness_target_map);
+        self.memory_blocks = caller_memory_blocks;
+        self.known_witnesses = caller_known_witnesses;
+
+        assert_eq!(
+            output_targets.len(),
+            return_targets.len(),
+            "Call outputs must match the return values of the called function"
+        );
+        for (output_target, return_target) in output_targets.iter().zip(return_targets.iter()) {
+            let return_target = self._gated_by_call_predicate(*return_target);
+            self.builder.connect(*output_target, return_target);
+            self.computed_targets.insert(*output_target);
+        }
+        self.call_predicate = enclosing_call_predicate;
+        self.inlined_calls.push(InlinedCall {
+            function_id,
+            witness_target_map: callee_witness_target_map,
+            predicate,
+            amount_of_nested_calls: self.inlined_calls.len() - first_nested_call,
+        });
+    }
+
+    // Inside a disabled call every witness is zero but its inputs, which may not satisfy the
+    // callee. So the callee receives zero inputs and outputs zero, and its assertions only hold
+    // while the call is enabled
+    fn _gated_by_call_predicate(self: &mut Self, target: Target) -> Target {
+        match self.call_predicate {
+            Some(call_predicate) => {
+                let gated_target = self.builder.mul(call_predicate.target, target);
+                self.computed_targets.insert(gated_target);
+                gated_target
+            }
+            None => target,
+        }
+    }
+
+    fn _translate_assert_zero(self: &mut Self, expression: &Expression) {
+        let mut translator = assert_zero_translator::AssertZeroTranslator::new_for(
+            &mut self.builder,
+            &mut self.witness_target_map,
+            &mut self.computed_targets,
+            expression,
+        );
+        let expression_witnesses = translator.witnesses();
+        match self.call_predicate {
+            // The prover gives every witness of an enabled call, so nothing is solved in-circuit
+            Some(call_predicate) => {
+                let expression_target = translator.translate_expression();
+                let gated_expression_target =
+                    self.builder.mul(call_predicate.target, expression_target);
+                self.builder.assert_zero(gated_expression_target);
+            }
+            None => translator.translate(&self.known_witnesses),
+        }
+        self.known_witnesses.extend(expression_witnesses);
+    }
+
+    fn _assert_constants_fit_in_goldilocks_field(self: &mut Self, circuit: &Circuit) {
+        let offending_opcodes =
+            constant_analysis::opcodes_with_constants_not_fitting_in_goldilocks_field(circuit);
+        if !offending_opcodes.is_empty() {
+            panic!(
+                "Constants that do not fit in the Goldilocks field were found in opcodes {:?}, try the --emulate-bn254 mode",
+                offending_opcodes
+            );
+        }
+    }
+
+    // Goldilocks elements are below 2^64, so a check of 64 bits or more would hold for every value
+    // in the circuit, even for a sum that wrapped around the Goldilocks modulus but overflows in
+    // the ACVM. Those checks need the BN254 limbs of the emulated mode
+    fn _range_check(self: &mut Self, target: Target, num_bits: usize) {
+        if num_bits >= F::BITS {
+            panic!(
+                "Range checks of {} bits cannot be made in the Goldilocks field, try the --emulate-bn254 mode",
+                num_bits
+            );
         }
+        self.builder.range_check(target, num_bits)
     }
 
     fn _extend_circuit_with_sha256_operation(
-        &self,
-        _inputs: &Vec<FunctionInput>,
-        _outputs: &Box<[Witness; 32]>,
+        self: &mut Self,
+        inputs: &Vec<FunctionInput>,
+        outputs: &Box<[Witness; 32]>,
     ) {
-        //         h = ['0x6a09e667', '0xbb67ae85', '0x3c6ef372', '0xa54ff53a', '0x510e527f', '0x9b05688c', '0x1f83d9ab', '0x5be0cd19']
-        //         k = ['0x428a2f98', '0x71374491', '0xb5c0fbcf', '0xe9b5dba5', '0x3956c25b', '0x59f111f1', '0x923f82a4','0xab1c5ed5', '0xd807aa98', '0x12835b01', '0x243185be', '0x550c7dc3', '0x72be5d74', '0x80deb1fe','0x9bdc06a7', '0xc19bf174', '0xe49b69c1', '0xefbe4786', '0x0fc19dc6', '0x240ca1cc', '0x2de92c6f','0x4a7484aa', '0x5cb0a9dc', '0x76f988da', '0x983e5152', '0xa831c66d', '0xb00327c8', '0xbf597fc7','0xc6e00bf3', '0xd5a79147', '0x06ca6351', '0x14292967', '0x27b70a85', '0x2e1b2138', '0x4d2c6dfc','0x53380d13', '0x650a7354', '0x766a0abb', '0x81c2c92e', '0x92722c85', '0xa2bfe8a1', '0xa81a664b','0xc24b8b70', '0xc76c51a3', '0xd192e819', '0xd6990624', '0xf40e3585', '0x106aa070', '0x19a4c116','0x1e376c08', '0x2748774c', '0x34b0bcb5', '0x391c0cb3', '0x4ed8aa4a', '0x5b9cca4f', '0x682e6ff3','0x748f82ee', '0x78a5636f', '0x84c87814', '0x8cc70208', '0x90befffa', '0xa4506ceb', '0xbef9a3f7','0xc67178f2']
+        let mut translator = sha256_translator::Sha256Translator::new_for(
+            &mut self.builder,
+            &mut self.witness_target_map,
+            inputs,
+            outputs,
+        );
+        translator.translate();
+        for output in outputs.iter() {
+            self.computed_targets
+                .insert(self.witness_target_map[output]);
+        }
+    }
+
+    fn _extend_circuit_with_recursive_aggregation(
+        self: &mut Self,
+        verification_key: &Vec<FunctionInput>,
+        proof: &Vec<FunctionInput>,
+        public_inputs: &Vec<FunctionInput>,
+        key_hash: &FunctionInput,
+    ) {
+        let inner_common_data = self._common_data_for_next_recursive_aggregation();
+        let mut translator =
+            recursive_aggregation_translator::RecursiveAggregationTranslator::new_for(
+                &mut self.builder,
+                &mut self.witness_target_map,
+                &inner_common_data,
+                verification_key,
+                proof,
+                public_inputs,
+                key_hash,
+                self.call_predicate,
+            );
+        translator.translate();
+    }
+
+    fn _common_data_for_next_recursive_aggregation(self: &mut Self) -> CommonCircuitData<F, D> {
+        let index = self.amount_of_recursive_aggregations;
+        self.amount_of_recursive_aggregations += 1;
+        match self.recursive_common_data.len() {
+            0 => panic!(
+                "Verifying a proof needs the verifying key of the inner program, given with --recursive-vk"
+            ),
+            1 => self.recursive_common_data[0].clone(),
+            _ => self
+                .recursive_common_data
+                .get(index)
+                .cloned()
+                .expect("There are more proofs to verify than verifying keys given with --recursive-vk"),
+        }
     }
 
     fn _extend_circuit_with_bitwise_operation(
@@ -159,7 +511,9 @@ impl CircuitBuilderFromAcirToPlonky2 {
             self._translate_bitwise_operation(lhs_binary_target, rhs_binary_target, operation);
 
         let output_target = self.convert_binary_number_to_number(output_binary_target);
-        self.witness_target_map.insert(*output, output_target);
+        let output_witness_target = self._get_or_create_target_for_witness(*output);
+        self.builder.connect(output_witness_target, output_target);
+        self.computed_targets.insert(output_witness_target);
     }
 
     fn _binary_number_target_for_witness(
@@ -205,6 +559,14 @@ impl CircuitBuilderFromAcirToPlonky2 {
         }
     }
 
+    // Return values are public inputs too, placed after the public parameters
+    fn _register_return_values_from_acir_circuit(self: &mut Self, circuit: &Circuit) {
+        for return_value_witness in return_value_witnesses(circuit, &self.return_witnesses) {
+            let return_value_target = self._get_or_create_target_for_witness(return_value_witness);
+            self.builder.register_public_input(return_value_target);
+        }
+    }
+
     fn _register_new_public_input_from_witness(
         self: &mut Self,
         public_input_witness: Witness,
@@ -216,25 +578,99 @@ impl CircuitBuilderFromAcirToPlonky2 {
         public_input_target
     }
 
-    fn _register_intermediate_witnesses_for_memory_op(self: &mut Self, op: &MemOp) {
-        let at = &op.index.linear_combinations[0].1;
-        self._get_or_create_target_for_witness(*at);
+    fn _register_memory_block(self: &mut Self, block_id: &BlockId, init: &Vec<Witness>) {
+        let init_targets: Vec<Target> = init
+            .iter()
+            .map(|witness| self._get_or_create_target_for_witness(*witness))
+            .collect();
+        self.memory_blocks.insert(
+            block_id.0,
+            MemoryBlock::new_with_initial_values(init_targets),
+        );
+    }
+
+    fn _register_memory_op(
+        self: &mut Self,
+        block_id: &BlockId,
+        op: &MemOp,
+        predicate: &Option<Expression>,
+    ) {
+        let index = self._translate_expression(&op.index);
+        let value = self._translate_expression(&op.value);
+        let access_kind = self._translate_memory_access_kind(&op.operation);
+        // Inside a disabled call the operation is disabled too, whatever its own predicate
+        let predicate = self._and_call_predicate(predicate);
 
-        let value = &op.value.linear_combinations[0].1;
-        self._get_or_create_target_for_witness(*value);
+        let memory_operation = MemoryOperation {
+            index,
+            value,
+            access_kind,
+            predicate,
+        };
+        self.memory_blocks
+            .get_mut(&block_id.0)
+            .expect("Memory block must be initialized before being accessed")
+            .operations
+            .push(memory_operation);
     }
 
-    fn _get_or_create_target_for_witness(self: &mut Self, witness: Witness) -> Target {
-        match self.witness_target_map.get(&witness) {
-            Some(target) => *target,
-            None => {
-                let target = self.builder.add_virtual_target();
-                self.witness_target_map.insert(witness, target);
-                target
+    fn _translate_memory_access_kind(self: &mut Self, operation: &Expression) -> AccessKind {
+        if operation.is_const() {
+            return match operation.q_c.is_zero() {
+                true => AccessKind::Read,
+                false => AccessKind::Write,
+            };
+        }
+        let is_write = self._translate_expression(operation);
+        let is_write = BoolTarget::new_unsafe(is_write);
+        self.builder.assert_bool(is_write);
+        AccessKind::Dynamic(is_write)
+    }
+
+    // The predicate of an opcode combined with the one of the call being inlined, if any
+    fn _and_call_predicate(self: &mut Self, predicate: &Option<Expression>) -> Option<BoolTarget> {
+        match (predicate, self.call_predicate) {
+            (None, call_predicate) => call_predicate,
+            (Some(predicate), None) => Some(self._translate_predicate(predicate)),
+            (Some(predicate), Some(call_predicate)) => {
+                let predicate = self._translate_predicate(predicate);
+                Some(self.builder.and(predicate, call_predicate))
             }
         }
     }
 
+    fn _translate_predicate(self: &mut Self, predicate: &Expression) -> BoolTarget {
+        let predicate_target = self._translate_expression(predicate);
+        let predicate_target = BoolTarget::new_unsafe(predicate_target);
+        self.builder.assert_bool(predicate_target);
+        predicate_target
+    }
+
+    fn _translate_expression(self: &mut Self, expression: &Expression) -> Target {
+        if let Some(witness) = expression.to_witness() {
+            return self._get_or_create_target_for_witness(witness);
+        }
+        let mut translator = assert_zero_translator::AssertZeroTranslator::new_for(
+            &mut self.builder,
+            &mut self.witness_target_map,
+            &mut self.computed_targets,
+            expression,
+        );
+        translator.translate_expression()
+    }
+
+    fn _translate_memory_blocks(self: &mut Self) {
+        for memory_block in self.memory_blocks.values() {
+            let mut translator =
+                memory_translator::MemoryTranslator::new_for(&mut self.builder, memory_block);
+            translator.translate();
+        }
+    }
+
+    fn _get_or_create_target_for_witness(self: &mut Self, witness: Witness) -> Target {
+        get_or_create_target_for_witness(&mut self.builder, &mut self.witness_target_map, witness)
+    }
+
     fn and(&mut self, b1: BoolTarget, b2: BoolTarget) -> BoolTarget {
         self.builder.and(b1, b2)
     }
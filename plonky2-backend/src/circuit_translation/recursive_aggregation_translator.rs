@@ -0,0 +1,276 @@
+use super::*;
+use plonky2::field::extension::{Extendable, FieldExtension};
+use plonky2::hash::hash_types::MerkleCapTarget;
+use plonky2::hash::merkle_proofs::{MerkleProof, MerkleProofTarget};
+use plonky2::hash::merkle_tree::MerkleCap;
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::iop::ext_target::ExtensionTarget;
+use plonky2::plonk::circuit_data::{
+    CommonCircuitData, VerifierCircuitTarget, VerifierOnlyCircuitData,
+};
+use plonky2::plonk::config::{Hasher, PoseidonGoldilocksConfig};
+use plonky2::plonk::proof::{Proof, ProofTarget};
+
+type InnerConfig = PoseidonGoldilocksConfig;
+type Extension = <F as Extendable<D>>::Extension;
+
+// Verifies a plonky2 proof of another program inside the circuit, like Noir's std::verify_proof.
+// The inner proof must use the Poseidon hash config, and its shape (gates, degree and FRI
+// parameters) is fixed by the common data of the inner program, while its verifying key, proof
+// and public inputs are witnesses
+pub struct RecursiveAggregationTranslator<'a> {
+    builder: &'a mut CircuitBuilder<F, D>,
+    witness_target_map: &'a mut HashMap<Witness, Target>,
+    inner_common_data: &'a CommonCircuitData<F, D>,
+    verification_key: &'a Vec<FunctionInput>,
+    proof: &'a Vec<FunctionInput>,
+    public_inputs: &'a Vec<FunctionInput>,
+    key_hash: &'a FunctionInput,
+    // Inside a disabled call there is no proof to verify, so a dummy proof is verified instead
+    call_predicate: Option<BoolTarget>,
+}
+
+impl<'a> RecursiveAggregationTranslator<'a> {
+    pub fn new_for(
+        builder: &'a mut CircuitBuilder<F, D>,
+        witness_target_map: &'a mut HashMap<Witness, Target>,
+        inner_common_data: &'a CommonCircuitData<F, D>,
+        verification_key: &'a Vec<FunctionInput>,
+        proof: &'a Vec<FunctionInput>,
+        public_inputs: &'a Vec<FunctionInput>,
+        key_hash: &'a FunctionInput,
+        call_predicate: Option<BoolTarget>,
+    ) -> RecursiveAggregationTranslator<'a> {
+        Self {
+            builder,
+            witness_target_map,
+            inner_common_data,
+            verification_key,
+            proof,
+            public_inputs,
+            key_hash,
+            call_predicate,
+        }
+    }
+
+    pub fn translate(&mut self) {
+        let verifier_data = self
+            .builder
+            .add_virtual_verifier_data(self.inner_common_data.config.fri_config.cap_height);
+        let proof = self
+            .builder
+            .add_virtual_proof_with_pis(self.inner_common_data);
+
+        let verification_key_targets = verifier_data_targets(&verifier_data);
+        self._connect_to_inputs(
+            "verification key",
+            &verification_key_targets,
+            self.verification_key,
+        );
+        self._connect_to_inputs("proof", &proof_targets(&proof.proof), self.proof);
+        self._connect_to_inputs("public inputs", &proof.public_inputs, self.public_inputs);
+
+        // ACIR gives a single field element for the key hash, see key_hash
+        let key_hash = self
+            .builder
+            .hash_n_to_hash_no_pad::<PoseidonHash>(verification_key_targets);
+        let key_hash_target = get_or_create_target_for_witness(
+            self.builder,
+            self.witness_target_map,
+            self.key_hash.witness,
+        );
+        let key_hash = match self.call_predicate {
+            Some(call_predicate) => self
+                .builder
+                .mul(call_predicate.target, key_hash.elements[0]),
+            None => key_hash.elements[0],
+        };
+        self.builder.connect(key_hash, key_hash_target);
+
+        match self.call_predicate {
+            Some(call_predicate) => self
+                .builder
+                .conditionally_verify_proof_or_dummy::<InnerConfig>(
+                    call_predicate,
+                    &proof,
+                    &verifier_data,
+                    self.inner_common_data,
+                )
+                .expect("There was a problem building a dummy proof of the inner program"),
+            None => self.builder.verify_proof::<InnerConfig>(
+                &proof,
+                &verifier_data,
+                self.inner_common_data,
+            ),
+        }
+    }
+
+    fn _connect_to_inputs(
+        self: &mut Self,
+        name: &str,
+        targets: &Vec<Target>,
+        inputs: &Vec<FunctionInput>,
+    ) {
+        if targets.len() != inputs.len() {
+            panic!(
+                "The inner program expects {} fields for the {}, but {} were given",
+                targets.len(),
+                name,
+                inputs.len()
+            );
+        }
+        for (target, input) in targets.iter().zip(inputs.iter()) {
+            let input_target = get_or_create_target_for_witness(
+                self.builder,
+                self.witness_target_map,
+                input.witness,
+            );
+            self.builder.connect(*target, input_target);
+        }
+    }
+}
+
+// The verifying key is given as fields in this order: the circuit digest followed by the
+// Merkle cap of the constants and sigmas
+pub fn verifier_data_targets(verifier_data: &VerifierCircuitTarget) -> Vec<Target> {
+    [
+        verifier_data.circuit_digest.elements.to_vec(),
+        _cap_targets(&verifier_data.constants_sigmas_cap),
+    ]
+    .concat()
+}
+
+// The proof is given as fields following the order of the plonky2 proof struct: the wires,
+// Z and quotient caps, the openings, and the FRI proof
+pub fn proof_targets(proof: &ProofTarget<D>) -> Vec<Target> {
+    let mut targets: Vec<Target> = Vec::new();
+    targets.extend(_cap_targets(&proof.wires_cap));
+    targets.extend(_cap_targets(&proof.plonk_zs_partial_products_cap));
+    targets.extend(_cap_targets(&proof.quotient_polys_cap));
+
+    let openings = &proof.openings;
+    for opened_values in [
+        &openings.constants,
+        &openings.plonk_sigmas,
+        &openings.wires,
+        &openings.plonk_zs,
+        &openings.plonk_zs_next,
+        &openings.lookup_zs,
+        &openings.next_lookup_zs,
+        &openings.partial_products,
+        &openings.quotient_polys,
+    ] {
+        targets.extend(_extension_targets(opened_values));
+    }
+
+    let fri_proof = &proof.opening_proof;
+    for cap in &fri_proof.commit_phase_merkle_caps {
+        targets.extend(_cap_targets(cap));
+    }
+    for query_round in &fri_proof.query_round_proofs {
+        for (evaluations, merkle_proof) in &query_round.initial_trees_proof.evals_proofs {
+            targets.extend(evaluations);
+            targets.extend(_merkle_proof_targets(merkle_proof));
+        }
+        for step in &query_round.steps {
+            targets.extend(_extension_targets(&step.evals));
+            targets.extend(_merkle_proof_targets(&step.merkle_proof));
+        }
+    }
+    targets.extend(_extension_targets(&fri_proof.final_poly.0));
+    targets.push(fri_proof.pow_witness);
+    targets
+}
+
+fn _cap_targets(cap: &MerkleCapTarget) -> Vec<Target> {
+    cap.0.iter().flat_map(|hash| hash.elements).collect()
+}
+
+fn _merkle_proof_targets(merkle_proof: &MerkleProofTarget) -> Vec<Target> {
+    merkle_proof
+        .siblings
+        .iter()
+        .flat_map(|hash| hash.elements)
+        .collect()
+}
+
+fn _extension_targets(extension_targets: &Vec<ExtensionTarget<D>>) -> Vec<Target> {
+    extension_targets
+        .iter()
+        .flat_map(|extension_target| extension_target.0)
+        .collect()
+}
+
+// The values of the fields above, for a verifying key and proof of the inner program. Both
+// orders must match, since Noir programs receive these values as the inputs of verify_proof
+pub fn verifier_data_fields(verifier_only: &VerifierOnlyCircuitData<InnerConfig, D>) -> Vec<F> {
+    [
+        verifier_only.circuit_digest.elements.to_vec(),
+        _cap_fields(&verifier_only.constants_sigmas_cap),
+    ]
+    .concat()
+}
+
+pub fn proof_fields(proof: &Proof<F, InnerConfig, D>) -> Vec<F> {
+    let mut fields: Vec<F> = Vec::new();
+    fields.extend(_cap_fields(&proof.wires_cap));
+    fields.extend(_cap_fields(&proof.plonk_zs_partial_products_cap));
+    fields.extend(_cap_fields(&proof.quotient_polys_cap));
+
+    let openings = &proof.openings;
+    for opened_values in [
+        &openings.constants,
+        &openings.plonk_sigmas,
+        &openings.wires,
+        &openings.plonk_zs,
+        &openings.plonk_zs_next,
+        &openings.lookup_zs,
+        &openings.lookup_zs_next,
+        &openings.partial_products,
+        &openings.quotient_polys,
+    ] {
+        fields.extend(_extension_fields(opened_values));
+    }
+
+    let fri_proof = &proof.opening_proof;
+    for cap in &fri_proof.commit_phase_merkle_caps {
+        fields.extend(_cap_fields(cap));
+    }
+    for query_round in &fri_proof.query_round_proofs {
+        for (evaluations, merkle_proof) in &query_round.initial_trees_proof.evals_proofs {
+            fields.extend(evaluations);
+            fields.extend(_merkle_proof_fields(merkle_proof));
+        }
+        for step in &query_round.steps {
+            fields.extend(_extension_fields(&step.evals));
+            fields.extend(_merkle_proof_fields(&step.merkle_proof));
+        }
+    }
+    fields.extend(_extension_fields(&fri_proof.final_poly.coeffs));
+    fields.push(fri_proof.pow_witness);
+    fields
+}
+
+// The first element of the Poseidon hash of the verifying key fields
+pub fn key_hash(verification_key: &Vec<F>) -> F {
+    PoseidonHash::hash_no_pad(verification_key).elements[0]
+}
+
+fn _cap_fields(cap: &MerkleCap<F, PoseidonHash>) -> Vec<F> {
+    cap.0.iter().flat_map(|hash| hash.elements).collect()
+}
+
+fn _merkle_proof_fields(merkle_proof: &MerkleProof<F, PoseidonHash>) -> Vec<F> {
+    merkle_proof
+        .siblings
+        .iter()
+        .flat_map(|hash| hash.elements)
+        .collect()
+}
+
+fn _extension_fields(extension_fields: &Vec<Extension>) -> Vec<F> {
+    extension_fields
+        .iter()
+        .flat_map(|extension_field| extension_field.to_basefield_array())
+        .collect()
+}
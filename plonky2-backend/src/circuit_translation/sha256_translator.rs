@@ -0,0 +1,312 @@
+use super::*;
+
+const WORD_BITS: usize = 32;
+const BLOCK_BYTES: usize = 64;
+// A sum of at most 7 words fits in 35 bits
+const MAX_CARRY_BITS: usize = 3;
+
+const INITIAL_HASH: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+pub struct Sha256Translator<'a> {
+    builder: &'a mut CircuitBuilder<F, D>,
+    witness_target_map: &'a mut HashMap<Witness, Target>,
+    inputs: &'a Vec<FunctionInput>,
+    outputs: &'a [Witness; 32],
+}
+
+impl<'a> Sha256Translator<'a> {
+    pub fn new_for(
+        builder: &'a mut CircuitBuilder<F, D>,
+        witness_target_map: &'a mut HashMap<Witness, Target>,
+        inputs: &'a Vec<FunctionInput>,
+        outputs: &'a [Witness; 32],
+    ) -> Sha256Translator<'a> {
+        Self {
+            builder,
+            witness_target_map,
+            inputs,
+            outputs,
+        }
+    }
+
+    pub fn translate(&mut self) {
+        let message = self._padded_message_bits();
+        let mut hash: Vec<BinaryDigitsTarget> = INITIAL_HASH
+            .iter()
+            .map(|word| self._constant_word(*word))
+            .collect();
+        for block in message.chunks(BLOCK_BYTES * 8) {
+            hash = self._compress_block(&hash, block);
+        }
+        self._connect_digest_to_outputs(&hash);
+    }
+
+    fn _padded_message_bits(self: &mut Self) -> Vec<BoolTarget> {
+        let mut message_bits: Vec<BoolTarget> = Vec::new();
+        for input in self.inputs {
+            assert!(
+                input.num_bits <= 8,
+                "SHA256 inputs with more than 8 bits are not supported"
+            );
+            let input_target = get_or_create_target_for_witness(
+                self.builder,
+                self.witness_target_map,
+                input.witness,
+            );
+            let mut input_bits = self.builder.split_le(input_target, input.num_bits as usize);
+            input_bits.resize(8, self.builder._false());
+            message_bits.extend(input_bits.into_iter().rev());
+        }
+
+        let message_length_in_bits = (self.inputs.len() * 8) as u64;
+        message_bits.push(self.builder._true());
+        while message_bits.len() % (BLOCK_BYTES * 8) != BLOCK_BYTES * 8 - 64 {
+            message_bits.push(self.builder._false());
+        }
+        for bit_position in (0..64).rev() {
+            let bit = (message_length_in_bits >> bit_position) & 1 == 1;
+            message_bits.push(self.builder.constant_bool(bit));
+        }
+        message_bits
+    }
+
+    fn _compress_block(
+        self: &mut Self,
+        hash: &Vec<BinaryDigitsTarget>,
+        block: &[BoolTarget],
+    ) -> Vec<BinaryDigitsTarget> {
+        let schedule = self._message_schedule(block);
+
+        let mut state = hash.clone();
+        for round in 0..64 {
+            state = self._round(&state, &schedule[round], ROUND_CONSTANTS[round]);
+        }
+
+        hash.iter()
+            .zip(state.iter())
+            .map(|(hash_word, state_word)| {
+                self._add_words(vec![hash_word.clone(), state_word.clone()], 0)
+            })
+            .collect()
+    }
+
+    fn _message_schedule(self: &mut Self, block: &[BoolTarget]) -> Vec<BinaryDigitsTarget> {
+        let mut schedule: Vec<BinaryDigitsTarget> = block
+            .chunks(WORD_BITS)
+            .map(|word_bits| BinaryDigitsTarget {
+                bits: word_bits.to_vec(),
+            })
+            .collect();
+        for t in 16..64 {
+            let s0 = self._small_sigma(&schedule[t - 15], 7, 18, 3);
+            let s1 = self._small_sigma(&schedule[t - 2], 17, 19, 10);
+            let word = self._add_words(
+                vec![s1, schedule[t - 7].clone(), s0, schedule[t - 16].clone()],
+                0,
+            );
+            schedule.push(word);
+        }
+        schedule
+    }
+
+    fn _round(
+        self: &mut Self,
+        state: &Vec<BinaryDigitsTarget>,
+        scheduled_word: &BinaryDigitsTarget,
+        round_constant: u32,
+    ) -> Vec<BinaryDigitsTarget> {
+        let (a, b, c, d) = (&state[0], &state[1], &state[2], &state[3]);
+        let (e, f, g, h) = (&state[4], &state[5], &state[6], &state[7]);
+
+        let big_sigma_1 = self._big_sigma(e, 6, 11, 25);
+        let choose = self._choose(e, f, g);
+        let big_sigma_0 = self._big_sigma(a, 2, 13, 22);
+        let majority = self._majority(a, b, c);
+
+        let temp_1_terms = vec![h.clone(), big_sigma_1, choose, scheduled_word.clone()];
+        let mut new_e_terms = temp_1_terms.clone();
+        new_e_terms.push(d.clone());
+        let mut new_a_terms = temp_1_terms;
+        new_a_terms.push(big_sigma_0);
+        new_a_terms.push(majority);
+
+        let new_e = self._add_words(new_e_terms, round_constant);
+        let new_a = self._add_words(new_a_terms, round_constant);
+
+        vec![
+            new_a,
+            a.clone(),
+            b.clone(),
+            c.clone(),
+            new_e,
+            e.clone(),
+            f.clone(),
+            g.clone(),
+        ]
+    }
+
+    fn _small_sigma(
+        self: &mut Self,
+        word: &BinaryDigitsTarget,
+        first_rotation: usize,
+        second_rotation: usize,
+        shift: usize,
+    ) -> BinaryDigitsTarget {
+        let first = Self::_rotate_right(word, first_rotation);
+        let second = Self::_rotate_right(word, second_rotation);
+        let third = self._shift_right(word, shift);
+        self._xor_3(&first, &second, &third)
+    }
+
+    fn _big_sigma(
+        self: &mut Self,
+        word: &BinaryDigitsTarget,
+        first_rotation: usize,
+        second_rotation: usize,
+        third_rotation: usize,
+    ) -> BinaryDigitsTarget {
+        let first = Self::_rotate_right(word, first_rotation);
+        let second = Self::_rotate_right(word, second_rotation);
+        let third = Self::_rotate_right(word, third_rotation);
+        self._xor_3(&first, &second, &third)
+    }
+
+    fn _rotate_right(word: &BinaryDigitsTarget, positions: usize) -> BinaryDigitsTarget {
+        BinaryDigitsTarget {
+            bits: (0..WORD_BITS)
+                .map(|i| word.bits[(i + WORD_BITS - positions) % WORD_BITS])
+                .collect(),
+        }
+    }
+
+    fn _shift_right(
+        self: &mut Self,
+        word: &BinaryDigitsTarget,
+        positions: usize,
+    ) -> BinaryDigitsTarget {
+        let zero = self.builder._false();
+        BinaryDigitsTarget {
+            bits: (0..WORD_BITS)
+                .map(|i| match i < positions {
+                    true => zero,
+                    false => word.bits[i - positions],
+                })
+                .collect(),
+        }
+    }
+
+    fn _xor_3(
+        self: &mut Self,
+        x: &BinaryDigitsTarget,
+        y: &BinaryDigitsTarget,
+        z: &BinaryDigitsTarget,
+    ) -> BinaryDigitsTarget {
+        BinaryDigitsTarget {
+            bits: (0..WORD_BITS)
+                .map(|i| {
+                    let x_xor_y = self._xor(x.bits[i], y.bits[i]);
+                    self._xor(x_xor_y, z.bits[i])
+                })
+                .collect(),
+        }
+    }
+
+    // x xor y = x + y - 2xy
+    fn _xor(self: &mut Self, x: BoolTarget, y: BoolTarget) -> BoolTarget {
+        let x_plus_y = self.builder.add(x.target, y.target);
+        let minus_two = -F::from_canonical_u64(2);
+        let xor = self
+            .builder
+            .arithmetic(minus_two, F::ONE, x.target, y.target, x_plus_y);
+        BoolTarget::new_unsafe(xor)
+    }
+
+    // ch(e, f, g) = (e and f) xor (not e and g)
+    fn _choose(
+        self: &mut Self,
+        e: &BinaryDigitsTarget,
+        f: &BinaryDigitsTarget,
+        g: &BinaryDigitsTarget,
+    ) -> BinaryDigitsTarget {
+        BinaryDigitsTarget {
+            bits: (0..WORD_BITS)
+                .map(|i| {
+                    let choice = self
+                        .builder
+                        .select(e.bits[i], f.bits[i].target, g.bits[i].target);
+                    BoolTarget::new_unsafe(choice)
+                })
+                .collect(),
+        }
+    }
+
+    // maj(a, b, c) = a ? (b or c) : (b and c)
+    fn _majority(
+        self: &mut Self,
+        a: &BinaryDigitsTarget,
+        b: &BinaryDigitsTarget,
+        c: &BinaryDigitsTarget,
+    ) -> BinaryDigitsTarget {
+        BinaryDigitsTarget {
+            bits: (0..WORD_BITS)
+                .map(|i| {
+                    let b_and_c = self.builder.and(b.bits[i], c.bits[i]);
+                    let b_or_c = self.builder.or(b.bits[i], c.bits[i]);
+                    let majority = self
+                        .builder
+                        .select(a.bits[i], b_or_c.target, b_and_c.target);
+                    BoolTarget::new_unsafe(majority)
+                })
+                .collect(),
+        }
+    }
+
+    fn _add_words(
+        self: &mut Self,
+        words: Vec<BinaryDigitsTarget>,
+        constant: u32,
+    ) -> BinaryDigitsTarget {
+        let mut sum = self.builder.constant(F::from_canonical_u32(constant));
+        for word in words {
+            let word_as_number = self.builder.le_sum(word.bits.into_iter().rev());
+            sum = self.builder.add(sum, word_as_number);
+        }
+        let sum_bits = self.builder.split_le(sum, WORD_BITS + MAX_CARRY_BITS);
+        BinaryDigitsTarget {
+            bits: sum_bits.into_iter().take(WORD_BITS).rev().collect(),
+        }
+    }
+
+    fn _constant_word(self: &mut Self, word: u32) -> BinaryDigitsTarget {
+        BinaryDigitsTarget {
+            bits: (0..WORD_BITS)
+                .rev()
+                .map(|bit_position| self.builder.constant_bool((word >> bit_position) & 1 == 1))
+                .collect(),
+        }
+    }
+
+    fn _connect_digest_to_outputs(self: &mut Self, hash: &Vec<BinaryDigitsTarget>) {
+        let digest_bits: Vec<BoolTarget> = hash.iter().flat_map(|word| word.bits.clone()).collect();
+        let outputs = self.outputs;
+        for (output, byte_bits) in outputs.iter().zip(digest_bits.chunks(8)) {
+            let byte = self.builder.le_sum(byte_bits.iter().rev());
+            let output_target =
+                get_or_create_target_for_witness(self.builder, self.witness_target_map, *output);
+            self.builder.connect(output_target, byte);
+        }
+    }
+}
@@ -1,6 +1,37 @@
-use plonky2::iop::target::BoolTarget;
+use plonky2::iop::target::{BoolTarget, Target};
 
 #[derive(Clone, Debug)]
 pub struct BinaryDigitsTarget {
     pub bits: Vec<BoolTarget>,
 }
+
+#[derive(Clone, Debug, Default)]
+pub struct MemoryAccessTarget {
+    pub index: Target,
+    pub timestamp: Target,
+    pub value: Target,
+    pub is_write: Target,
+}
+
+impl MemoryAccessTarget {
+    pub const NUMBER_OF_TARGETS: usize = 4;
+
+    pub fn targets(&self) -> Vec<Target> {
+        vec![self.index, self.timestamp, self.value, self.is_write]
+    }
+
+    pub fn from_targets(targets: &[Target]) -> Self {
+        Self {
+            index: targets[0],
+            timestamp: targets[1],
+            value: targets[2],
+            is_write: targets[3],
+        }
+    }
+}
+
+// A BN254 scalar represented with little-endian 16-bit limbs
+#[derive(Clone, Debug)]
+pub struct Bn254Target {
+    pub limbs: Vec<Target>,
+}
@@ -11,6 +11,23 @@ pub fn x_equals_0_opcode(public_input_witness: Witness) -> Opcode {
     })
 }
 
+pub fn circuit_with_opcodes(
+    opcodes: Vec<Opcode>,
+    public_parameters: Vec<Witness>,
+    return_values: Vec<Witness>,
+) -> Circuit {
+    Circuit {
+        current_witness_index: 0,
+        expression_width: ExpressionWidth::Unbounded,
+        opcodes,
+        private_parameters: BTreeSet::new(),
+        public_parameters: PublicInputs(BTreeSet::from_iter(public_parameters)),
+        return_values: PublicInputs(BTreeSet::from_iter(return_values)),
+        assert_messages: Default::default(),
+        recursive: false,
+    }
+}
+
 pub fn circuit_with_single_opcode(
     only_expr: Opcode,
     public_input_witnesses: Vec<Witness>,
@@ -348,23 +365,52 @@ pub fn sha256_circuit_with_inputs(
         })
         .collect();
 
+    let mut opcodes: Vec<Opcode> = public_input_witnesses
+        .iter()
+        .map(|w| black_box_range_opcode(*w, 8u32))
+        .collect();
+    opcodes.push(Opcode::BlackBoxFuncCall(
+        opcodes::BlackBoxFuncCall::SHA256 {
+            inputs: function_inputs,
+            outputs: Box::new(output_witnesses),
+        },
+    ));
+
     Circuit {
         current_witness_index: 0,
         expression_width: ExpressionWidth::Unbounded,
-        opcodes: vec![
-            black_box_range_opcode(public_input_witnesses[0], 8u32),
-            black_box_range_opcode(public_input_witnesses[1], 8u32),
-            black_box_range_opcode(public_input_witnesses[2], 8u32),
-            black_box_range_opcode(public_input_witnesses[3], 8u32),
-            Opcode::BlackBoxFuncCall(opcodes::BlackBoxFuncCall::SHA256 {
-                inputs: function_inputs,
-                outputs: Box::new(output_witnesses),
-            }),
-        ],
+        opcodes,
         private_parameters: BTreeSet::new(),
         public_parameters: PublicInputs(BTreeSet::from_iter(public_input_witnesses)),
-        return_values: PublicInputs(BTreeSet::from_iter([Witness(2)])),
+        return_values: PublicInputs(BTreeSet::from_iter(output_witnesses)),
         assert_messages: Default::default(),
         recursive: false,
     }
 }
+
+pub fn recursive_aggregation_circuit(
+    verification_key_witnesses: Vec<Witness>,
+    proof_witnesses: Vec<Witness>,
+    public_input_witnesses: Vec<Witness>,
+    key_hash_witness: Witness,
+) -> Circuit {
+    let function_inputs = |witnesses: &Vec<Witness>| -> Vec<FunctionInput> {
+        witnesses
+            .iter()
+            .map(|w| FunctionInput {
+                witness: *w,
+                num_bits: FieldElement::max_num_bits(),
+            })
+            .collect()
+    };
+    let opcode = Opcode::BlackBoxFuncCall(opcodes::BlackBoxFuncCall::RecursiveAggregation {
+        verification_key: function_inputs(&verification_key_witnesses),
+        proof: function_inputs(&proof_witnesses),
+        public_inputs: function_inputs(&public_input_witnesses),
+        key_hash: FunctionInput {
+            witness: key_hash_witness,
+            num_bits: FieldElement::max_num_bits(),
+        },
+    });
+    circuit_with_single_opcode(opcode, public_input_witnesses)
+}
@@ -1,4 +1,5 @@
 use super::*;
 
 pub mod circuit_factory;
+pub mod plonky2_factory;
 pub mod utils;
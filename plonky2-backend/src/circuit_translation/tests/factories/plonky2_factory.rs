@@ -0,0 +1,43 @@
+use super::*;
+use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+use plonky2::plonk::proof::{CompressedProofWithPublicInputs, ProofWithPublicInputs};
+
+// A plonky2 program proving knowledge of x such that x^exponent is its public input, used as
+// the program whose proofs get verified, aggregated or recursively verified
+pub fn power_circuit<C: GenericConfig<D, F = F>>(exponent: u64) -> (CircuitData<F, C, D>, Target) {
+    power_circuit_with_config(exponent, CircuitConfig::standard_recursion_config())
+}
+
+pub fn power_circuit_with_config<C: GenericConfig<D, F = F>>(
+    exponent: u64,
+    config: CircuitConfig,
+) -> (CircuitData<F, C, D>, Target) {
+    let mut builder = CircuitBuilder::<F, D>::new(config);
+    let x = builder.add_virtual_target();
+    let power = builder.exp_u64(x, exponent);
+    builder.register_public_input(power);
+    (builder.build::<C>(), x)
+}
+
+pub fn power_proof<C: GenericConfig<D, F = F>>(
+    circuit: &(CircuitData<F, C, D>, Target),
+    x: u64,
+) -> ProofWithPublicInputs<F, C, D> {
+    let (circuit_data, x_target) = circuit;
+    let mut witnesses = PartialWitness::<F>::new();
+    witnesses.set_target(*x_target, F::from_canonical_u64(x));
+    circuit_data.prove(witnesses).unwrap()
+}
+
+pub fn compressed_power_proof<C: GenericConfig<D, F = F>>(
+    circuit: &(CircuitData<F, C, D>, Target),
+    x: u64,
+) -> CompressedProofWithPublicInputs<F, C, D> {
+    let circuit_data = &circuit.0;
+    power_proof(circuit, x)
+        .compress(
+            &circuit_data.verifier_only.circuit_digest,
+            &circuit_data.common,
+        )
+        .unwrap()
+}
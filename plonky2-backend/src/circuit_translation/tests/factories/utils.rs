@@ -1,10 +1,17 @@
 use super::*;
 use crate::circuit_translation;
+use crate::circuit_translation::emulated_bn254_translator::{
+    bn254_limbs_of, CircuitBuilderFromAcirToEmulatedBn254,
+};
+use crate::circuit_translation::targets::Bn254Target;
 use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2::iop::witness::PartialWitness;
 use plonky2::iop::witness::WitnessWrite;
+use plonky2::plonk::config::KeccakGoldilocksConfig;
 use plonky2::plonk::proof::ProofWithPublicInputs;
 
+type C = KeccakGoldilocksConfig;
+
 pub fn generate_plonky2_circuit_from_acir_circuit(
     circuit: &Circuit,
 ) -> (CircuitData<F, C, 2>, HashMap<Witness, Target>) {
@@ -25,3 +32,59 @@ pub fn generate_plonky2_proof_using_witness_values(
     }
     circuit_data.prove(witnesses).unwrap()
 }
+
+pub fn generate_emulated_bn254_circuit_from_acir_circuit(
+    circuit: &Circuit,
+) -> (CircuitData<F, C, 2>, HashMap<Witness, Bn254Target>) {
+    let mut translator = CircuitBuilderFromAcirToEmulatedBn254::new();
+    translator.translate_circuit(circuit);
+    translator.unpack()
+}
+
+pub fn generate_emulated_bn254_proof_using_witness_values(
+    witness_assignment: Vec<(Witness, FieldElement)>,
+    witness_target_map: &HashMap<Witness, Bn254Target>,
+    circuit_data: &CircuitData<F, C, 2>,
+) -> ProofWithPublicInputs<GoldilocksField, C, 2> {
+    let mut witnesses = PartialWitness::<F>::new();
+    for (witness, value) in witness_assignment {
+        let bn254_target = witness_target_map.get(&witness).unwrap();
+        witnesses.set_target_arr(&bn254_target.limbs, &bn254_limbs_of(&value));
+    }
+    circuit_data.prove(witnesses).unwrap()
+}
+
+pub fn generate_plonky2_circuit_from_acir_program(
+    program: &Program,
+) -> (
+    CircuitData<F, C, 2>,
+    HashMap<Witness, Target>,
+    Vec<InlinedCall>,
+    HashSet<Target>,
+) {
+    let mut translator = circuit_translation::CircuitBuilderFromAcirToPlonky2::new();
+    translator.translate_program(program);
+    translator.unpack_program()
+}
+
+pub fn generate_plonky2_proof_using_witness_values_of_each_function(
+    witness_assignments: Vec<(Vec<(Witness, F)>, &HashMap<Witness, Target>)>,
+    circuit_data: &CircuitData<F, C, 2>,
+) -> ProofWithPublicInputs<GoldilocksField, C, 2> {
+    let mut witnesses = PartialWitness::<F>::new();
+    for (witness_assignment, witness_target_map) in witness_assignments {
+        for (witness, value) in witness_assignment {
+            let plonky2_target = witness_target_map.get(&witness).unwrap();
+            witnesses.set_target(*plonky2_target, value);
+        }
+    }
+    circuit_data.prove(witnesses).unwrap()
+}
+
+pub fn witness_map_from_values(values: Vec<(u32, u128)>) -> WitnessMap {
+    WitnessMap::from(BTreeMap::from_iter(
+        values
+            .into_iter()
+            .map(|(witness, value)| (Witness(witness), FieldElement::from(value))),
+    ))
+}
@@ -8,5 +8,20 @@ mod test_assert_zero;
 #[cfg(test)]
 mod test_blackbox;
 
+#[cfg(test)]
+mod test_calls;
+
+#[cfg(test)]
+mod test_constant_analysis;
+
+#[cfg(test)]
+mod test_emulated_bn254;
+
 #[cfg(test)]
 mod test_memory_operations;
+
+#[cfg(test)]
+mod test_public_inputs;
+
+#[cfg(test)]
+mod test_recursive_aggregation;
@@ -1,6 +1,8 @@
 use super::factories::circuit_factory::*;
 use super::factories::utils;
 use super::*;
+use acir::circuit::PublicInputs;
+use std::collections::BTreeSet;
 
 #[test]
 fn test_plonky2_vm_can_traslate_the_assert_x_equals_zero_program() {
@@ -260,9 +262,8 @@ fn test_plonky2_vm_can_translate_circuits_with_2_assert_zero_opcodes() {
 
     // Then
     let one = F::from_canonical_u64(1);
-    let five = F::from_canonical_u64(5);
     let proof = utils::generate_plonky2_proof_using_witness_values(
-        vec![(public_input_witness, one), (intermediate_witness, five)],
+        vec![(public_input_witness, one)],
         &witness_target_map,
         &circuit_data,
     );
@@ -271,3 +272,119 @@ fn test_plonky2_vm_can_translate_circuits_with_2_assert_zero_opcodes() {
     assert!(circuit_data.verify(proof).is_ok());
 }
 
+// fn main(x: pub Field, y: Field) -> pub Field {
+//     x * y + 2
+// }
+fn _x_times_y_plus_2_circuit(x: Witness, y: Witness, result: Witness) -> Circuit {
+    let only_opcode = Opcode::AssertZero(Expression {
+        mul_terms: vec![(FieldElement::one(), x, y)],
+        linear_combinations: vec![(-FieldElement::one(), result)],
+        q_c: FieldElement::from_hex("0x02").unwrap(),
+    });
+    let mut circuit = circuit_with_single_opcode(only_opcode, vec![x]);
+    circuit.private_parameters = BTreeSet::from([y]);
+    circuit.return_values = PublicInputs(BTreeSet::from([result]));
+    circuit
+}
+
+fn _program_with_main(circuit: Circuit) -> Program {
+    Program {
+        functions: vec![circuit],
+        unconstrained_functions: vec![],
+    }
+}
+
+#[test]
+fn test_plonky2_vm_solves_an_intermediate_witness_multiplied_by_a_known_one() {
+    // Given
+    let (x, y, result) = (Witness(0), Witness(1), Witness(2));
+    let circuit = _x_times_y_plus_2_circuit(x, y, result);
+
+    // When
+    let (circuit_data, witness_target_map) =
+        utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let three = F::from_canonical_u64(3);
+    let four = F::from_canonical_u64(4);
+    let proof = utils::generate_plonky2_proof_using_witness_values(
+        vec![(x, three), (y, four)],
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert_eq!(F::from_canonical_u64(14), proof.public_inputs[1]);
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_plonky2_vm_does_not_solve_a_private_input() {
+    // Given
+    let (x, y, result) = (Witness(0), Witness(1), Witness(2));
+    let program = _program_with_main(_x_times_y_plus_2_circuit(x, y, result));
+
+    // When
+    let (_, witness_target_map, _, computed_targets) =
+        utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    // Then
+    assert!(!computed_targets.contains(&witness_target_map[&y]));
+    assert!(computed_targets.contains(&witness_target_map[&result]));
+}
+
+#[test]
+#[should_panic(expected = "its coefficient is zero")]
+fn test_plonky2_vm_fails_to_solve_a_witness_whose_coefficient_is_zero() {
+    // fn main(x: pub Field) -> pub Field {
+    //     2 / x
+    // }
+
+    // Given
+    let (x, result) = (Witness(0), Witness(1));
+    let only_opcode = Opcode::AssertZero(Expression {
+        mul_terms: vec![(FieldElement::one(), x, result)],
+        linear_combinations: vec![],
+        q_c: -FieldElement::from_hex("0x02").unwrap(),
+    });
+    let mut circuit = circuit_with_single_opcode(only_opcode, vec![x]);
+    circuit.return_values = PublicInputs(BTreeSet::from([result]));
+
+    // When
+    let (circuit_data, witness_target_map) =
+        utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    utils::generate_plonky2_proof_using_witness_values(
+        vec![(x, F::ZERO)],
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_plonky2_vm_does_not_solve_a_squared_intermediate_witness() {
+    // Given
+    let public_input_witness = Witness(0);
+    let intermediate_witness = Witness(1);
+    let only_opcode = Opcode::AssertZero(Expression {
+        mul_terms: vec![(
+            FieldElement::one(),
+            intermediate_witness,
+            intermediate_witness,
+        )],
+        linear_combinations: vec![(-FieldElement::one(), public_input_witness)],
+        q_c: FieldElement::zero(),
+    });
+    let circuit = circuit_with_single_opcode(only_opcode, vec![public_input_witness]);
+
+    // When
+    let (circuit_data, witness_target_map) =
+        utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    utils::generate_plonky2_proof_using_witness_values(
+        vec![(public_input_witness, F::from_canonical_u64(9))],
+        &witness_target_map,
+        &circuit_data,
+    );
+}
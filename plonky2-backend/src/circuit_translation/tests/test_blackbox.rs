@@ -62,14 +62,35 @@ fn test_backend_cannot_provide_witness_value_bigger_than_u32_for_u32_range_check
 }
 
 #[test]
-#[should_panic(
-    expected = "Range checks with more than 32 bits are not allowed yet while using Plonky2 prover"
-)]
-fn test_backend_does_not_support_range_check_for_u64_or_bigger() {
+fn test_backend_can_translate_blackbox_func_call_range_check_u63() {
+    let max_num_bits = 63;
+    let max_allowed_witness_value = 2u64.pow(max_num_bits.clone()) - 1;
+    let max_allowed_witness_value_field = F::from_canonical_u64(max_allowed_witness_value);
+    test_range_check_with_witness_value(max_allowed_witness_value_field, max_num_bits);
+}
+
+#[test]
+#[should_panic]
+fn test_backend_cannot_provide_witness_value_bigger_than_u63_for_u63_range_check() {
+    let max_num_bits = 63;
+    let min_not_allowed_witness_value = 2u64.pow(max_num_bits.clone());
+    let min_not_allowed_witness_value_field = F::from_canonical_u64(min_not_allowed_witness_value);
+    test_range_check_with_witness_value(min_not_allowed_witness_value_field, max_num_bits);
+}
+
+// Goldilocks elements are below 2^64, so these checks would hold for any value in the circuit
+#[test]
+#[should_panic(expected = "Range checks of 64 bits cannot be made in the Goldilocks field")]
+fn test_backend_rejects_a_u64_range_check() {
     let max_num_bits = 64;
-    let goldilocks_max_value = (2u128.pow(64) - 2u128.pow(32)) as u64;
-    let goldilocks_max_value_field = F::from_noncanonical_u64(goldilocks_max_value.into());
-    test_range_check_with_witness_value(goldilocks_max_value_field, max_num_bits);
+    test_range_check_with_witness_value(F::ZERO, max_num_bits);
+}
+
+#[test]
+#[should_panic(expected = "Range checks of 128 bits cannot be made in the Goldilocks field")]
+fn test_backend_rejects_a_u128_range_check() {
+    let max_num_bits = 128;
+    test_range_check_with_witness_value(F::ZERO, max_num_bits);
 }
 
 fn test_range_check_with_witness_value(witness_value: F, max_num_bits: u32) {
@@ -242,6 +263,7 @@ fn _assert_backend_supports_bitwise_operation(
         &circuit_data,
     );
 
+    assert_eq!(output, proof.public_inputs[2]);
     assert!(circuit_data.verify(proof).is_ok());
 }
 
@@ -292,3 +314,104 @@ fn test_backend_can_translate_sha256_acir_opcode_with_short_input() {
 
     assert!(circuit_data.verify(proof).is_ok());
 }
+
+#[test]
+#[should_panic]
+fn test_backend_cannot_prove_a_wrong_sha256_digest() {
+    // fn main(in: [u8, 4]) -> pub [u8, 32]{
+    //     std::hash::sha256(in, 4);
+    // }
+
+    // Given
+    let message = b"hola";
+    let public_input_witnesses: Vec<Witness> = (0..4).map(Witness).collect();
+    let output_witnesses: [Witness; 32] =
+        (4..36).map(Witness).collect::<Vec<_>>().try_into().unwrap();
+    let circuit = circuit_factory::sha256_circuit_with_inputs(
+        public_input_witnesses.clone(),
+        output_witnesses,
+    );
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    //Then
+    let wrong_digest = _sha256_digest_as_field_elements(b"chau");
+    let mut witness_assignment = _message_as_witness_assignment(&public_input_witnesses, message);
+    witness_assignment.extend(output_witnesses.into_iter().zip(wrong_digest));
+
+    generate_plonky2_proof_using_witness_values(
+        witness_assignment,
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+#[test]
+fn test_backend_can_translate_sha256_acir_opcode_with_input_that_needs_two_blocks() {
+    // fn main(in: [u8, 56]) -> pub [u8, 32]{
+    //     std::hash::sha256(in, 56);
+    // }
+    _assert_backend_computes_sha256_of(&[0x61; 56]);
+}
+
+#[test]
+fn test_backend_can_translate_sha256_acir_opcode_with_input_longer_than_a_block() {
+    // fn main(in: [u8, 150]) -> pub [u8, 32]{
+    //     std::hash::sha256(in, 150);
+    // }
+    let message: Vec<u8> = (0..150).map(|i| (i * 7 % 256) as u8).collect();
+    _assert_backend_computes_sha256_of(&message);
+}
+
+fn _assert_backend_computes_sha256_of(message: &[u8]) {
+    // Given
+    let message_length = message.len() as u32;
+    let public_input_witnesses: Vec<Witness> = (0..message_length).map(Witness).collect();
+    let output_witnesses: [Witness; 32] = (message_length..message_length + 32)
+        .map(Witness)
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    let circuit = circuit_factory::sha256_circuit_with_inputs(
+        public_input_witnesses.clone(),
+        output_witnesses,
+    );
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    //Then
+    let mut witness_assignment = _message_as_witness_assignment(&public_input_witnesses, message);
+    witness_assignment.extend(
+        output_witnesses
+            .into_iter()
+            .zip(_sha256_digest_as_field_elements(message)),
+    );
+
+    let proof = generate_plonky2_proof_using_witness_values(
+        witness_assignment,
+        &witness_target_map,
+        &circuit_data,
+    );
+
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+fn _message_as_witness_assignment(witnesses: &Vec<Witness>, message: &[u8]) -> Vec<(Witness, F)> {
+    witnesses
+        .iter()
+        .zip(message.iter())
+        .map(|(witness, byte)| (*witness, F::from_canonical_u8(*byte)))
+        .collect()
+}
+
+fn _sha256_digest_as_field_elements(message: &[u8]) -> Vec<F> {
+    let mut hasher = Sha256::new();
+    hasher.update(message);
+    hasher
+        .finalize()
+        .into_iter()
+        .map(|b| F::from_canonical_u8(b))
+        .collect()
+}
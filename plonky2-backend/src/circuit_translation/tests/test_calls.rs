@@ -0,0 +1,419 @@
+use super::factories::circuit_factory::*;
+use super::factories::utils;
+use super::*;
+use acir::circuit::opcodes::{BlockId, BlockType, MemOp};
+use acir::circuit::{ExpressionWidth, PublicInputs};
+use std::collections::BTreeSet;
+
+// fn main(x: pub Field, y: Field) {
+//     assert(product(x, y) == 12);
+// }
+// #[fold]
+// fn product(a: Field, b: Field) -> Field { a * b }
+fn _product_program(predicate: Option<Expression>) -> Program {
+    let main_opcodes = vec![
+        Opcode::Call {
+            id: 1,
+            inputs: vec![Witness(0), Witness(1)],
+            outputs: vec![Witness(2)],
+            predicate,
+        },
+        Opcode::AssertZero(Expression {
+            mul_terms: vec![],
+            linear_combinations: vec![(FieldElement::one(), Witness(2))],
+            q_c: -FieldElement::from(12u128),
+        }),
+    ];
+    let mut main = circuit_with_single_opcode(x_equals_0_opcode(Witness(0)), vec![Witness(0)]);
+    main.opcodes = main_opcodes;
+
+    Program {
+        functions: vec![main, _product_function()],
+        unconstrained_functions: vec![],
+    }
+}
+
+fn _product_function() -> Circuit {
+    Circuit {
+        current_witness_index: 2,
+        expression_width: ExpressionWidth::Unbounded,
+        opcodes: vec![Opcode::AssertZero(Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(0), Witness(1))],
+            linear_combinations: vec![(-FieldElement::one(), Witness(2))],
+            q_c: FieldElement::zero(),
+        })],
+        private_parameters: BTreeSet::from([Witness(0), Witness(1)]),
+        public_parameters: PublicInputs(BTreeSet::new()),
+        return_values: PublicInputs(BTreeSet::from([Witness(2)])),
+        assert_messages: Default::default(),
+        recursive: false,
+    }
+}
+
+fn _field_values(values: Vec<(u32, u64)>) -> Vec<(Witness, F)> {
+    values
+        .into_iter()
+        .map(|(witness, value)| (Witness(witness), F::from_canonical_u64(value)))
+        .collect()
+}
+
+#[test]
+fn test_plonky2_backend_can_inline_a_function_call() {
+    // Given
+    let program = _product_program(None);
+
+    // When
+    let (circuit_data, witness_target_map, inlined_calls, _) =
+        utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    //Then
+    assert_eq!(1, inlined_calls.len());
+    assert_eq!(1, inlined_calls[0].function_id);
+    let proof = utils::generate_plonky2_proof_using_witness_values_of_each_function(
+        vec![
+            (
+                _field_values(vec![(0, 3), (1, 4), (2, 12)]),
+                &witness_target_map,
+            ),
+            (
+                _field_values(vec![(0, 3), (1, 4), (2, 12)]),
+                &inlined_calls[0].witness_target_map,
+            ),
+        ],
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_plonky2_backend_fails_if_the_called_function_is_not_satisfied() {
+    // Given
+    let program = _product_program(None);
+
+    // When
+    let (circuit_data, witness_target_map, inlined_calls, _) =
+        utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    //Then
+    utils::generate_plonky2_proof_using_witness_values_of_each_function(
+        vec![
+            (
+                _field_values(vec![(0, 2), (1, 4), (2, 12)]),
+                &witness_target_map,
+            ),
+            (
+                _field_values(vec![(0, 2), (1, 4), (2, 12)]),
+                &inlined_calls[0].witness_target_map,
+            ),
+        ],
+        &circuit_data,
+    );
+}
+
+#[test]
+fn test_plonky2_backend_inlines_a_call_with_a_true_constant_predicate() {
+    // Given
+    let program = _product_program(Some(Expression::one()));
+
+    // When
+    let (_, _, inlined_calls, _) = utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    //Then
+    assert_eq!(1, inlined_calls.len());
+}
+
+#[test]
+fn test_plonky2_backend_does_not_inline_a_call_with_a_false_constant_predicate() {
+    // Given
+    let program = _product_program(Some(Expression::zero()));
+
+    // When
+    let (_, _, inlined_calls, _) = utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    //Then
+    assert!(inlined_calls.is_empty());
+}
+
+// fn main(x: pub Field, y: Field, enabled: bool) -> pub Field {
+//     if enabled { product(x, y) } else { 0 }
+// }
+fn _product_program_with_unchecked_output(predicate: Expression) -> Program {
+    let mut program = _product_program(Some(predicate));
+    program.functions[0].opcodes.truncate(1);
+    program.functions[0].return_values = PublicInputs(BTreeSet::from([Witness(2)]));
+    program
+}
+
+#[test]
+fn test_plonky2_backend_sets_the_outputs_of_a_disabled_call_to_zero() {
+    // Given
+    let program = _product_program_with_unchecked_output(Expression::zero());
+
+    // When
+    let (circuit_data, witness_target_map, _, _) =
+        utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    //Then
+    let proof = utils::generate_plonky2_proof_using_witness_values(
+        _field_values(vec![(0, 3), (2, 0)]),
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert_eq!(F::ZERO, proof.public_inputs[1]);
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_plonky2_backend_inlines_a_call_enabled_by_a_witness_predicate() {
+    // Given
+    let program = _product_program(Some(Expression::from(Witness(3))));
+
+    // When
+    let (circuit_data, witness_target_map, inlined_calls, _) =
+        utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    //Then
+    assert_eq!(1, inlined_calls.len());
+    let proof = utils::generate_plonky2_proof_using_witness_values_of_each_function(
+        vec![
+            (
+                _field_values(vec![(0, 3), (1, 4), (2, 12), (3, 1)]),
+                &witness_target_map,
+            ),
+            (
+                _field_values(vec![(2, 12)]),
+                &inlined_calls[0].witness_target_map,
+            ),
+        ],
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_plonky2_backend_does_not_check_a_call_disabled_by_a_witness_predicate() {
+    // Given
+    let program = _product_program_with_unchecked_output(Expression::from(Witness(3)));
+
+    // When
+    let (circuit_data, witness_target_map, inlined_calls, _) =
+        utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    //Then
+    let proof = utils::generate_plonky2_proof_using_witness_values_of_each_function(
+        vec![
+            (
+                _field_values(vec![(0, 3), (1, 4), (2, 0), (3, 0)]),
+                &witness_target_map,
+            ),
+            (
+                _field_values(vec![(2, 5)]),
+                &inlined_calls[0].witness_target_map,
+            ),
+        ],
+        &circuit_data,
+    );
+    assert_eq!(F::ZERO, proof.public_inputs[1]);
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_plonky2_backend_fails_if_a_disabled_call_has_a_non_zero_output() {
+    // Given
+    let program = _product_program_with_unchecked_output(Expression::from(Witness(3)));
+
+    // When
+    let (circuit_data, witness_target_map, inlined_calls, _) =
+        utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    //Then
+    utils::generate_plonky2_proof_using_witness_values_of_each_function(
+        vec![
+            (
+                _field_values(vec![(0, 3), (1, 4), (2, 12), (3, 0)]),
+                &witness_target_map,
+            ),
+            (
+                _field_values(vec![(2, 12)]),
+                &inlined_calls[0].witness_target_map,
+            ),
+        ],
+        &circuit_data,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_plonky2_backend_fails_if_a_witness_predicate_is_not_boolean() {
+    // Given
+    let program = _product_program_with_unchecked_output(Expression::from(Witness(3)));
+
+    // When
+    let (circuit_data, witness_target_map, inlined_calls, _) =
+        utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    //Then
+    utils::generate_plonky2_proof_using_witness_values_of_each_function(
+        vec![
+            (
+                _field_values(vec![(0, 3), (1, 4), (2, 24), (3, 2)]),
+                &witness_target_map,
+            ),
+            (
+                _field_values(vec![(2, 12)]),
+                &inlined_calls[0].witness_target_map,
+            ),
+        ],
+        &circuit_data,
+    );
+}
+
+#[test]
+fn test_plonky2_backend_inlines_every_call_to_the_same_function_separately() {
+    // Given
+    let mut program = _product_program(None);
+    program.functions[0].opcodes.push(Opcode::Call {
+        id: 1,
+        inputs: vec![Witness(2), Witness(0)],
+        outputs: vec![Witness(3)],
+        predicate: None,
+    });
+
+    // When
+    let (circuit_data, witness_target_map, inlined_calls, _) =
+        utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    //Then
+    assert_eq!(2, inlined_calls.len());
+    let proof = utils::generate_plonky2_proof_using_witness_values_of_each_function(
+        vec![
+            (
+                _field_values(vec![(0, 3), (1, 4), (2, 12), (3, 36)]),
+                &witness_target_map,
+            ),
+            (
+                _field_values(vec![(0, 3), (1, 4), (2, 12)]),
+                &inlined_calls[0].witness_target_map,
+            ),
+            (
+                _field_values(vec![(0, 12), (1, 3), (2, 36)]),
+                &inlined_calls[1].witness_target_map,
+            ),
+        ],
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+// fn main(x: Field, enabled: bool) -> pub Field {
+//     if enabled { overwrite(x) } else { 0 }
+// }
+// #[fold]
+// fn overwrite(a: Field) -> Field {
+//     let mut array = [a];
+//     array[0] = 7;
+//     array[0]
+// }
+fn _overwrite_program() -> Program {
+    let main = circuit_with_opcodes(
+        vec![Opcode::Call {
+            id: 1,
+            inputs: vec![Witness(0)],
+            outputs: vec![Witness(1)],
+            predicate: Some(Expression::from(Witness(2))),
+        }],
+        vec![],
+        vec![Witness(1)],
+    );
+    let overwrite = circuit_with_opcodes(
+        vec![
+            Opcode::MemoryInit {
+                block_id: BlockId(0),
+                init: vec![Witness(0)],
+                block_type: BlockType::Memory,
+            },
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp {
+                    operation: Expression::one(),
+                    index: Expression::zero(),
+                    value: Expression::from_field(FieldElement::from(7u128)),
+                },
+                predicate: None,
+            },
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp {
+                    operation: Expression::zero(),
+                    index: Expression::zero(),
+                    value: Expression::from(Witness(1)),
+                },
+                predicate: None,
+            },
+        ],
+        vec![],
+        vec![Witness(1)],
+    );
+    Program {
+        functions: vec![main, overwrite],
+        unconstrained_functions: vec![],
+    }
+}
+
+#[test]
+fn test_plonky2_backend_writes_to_memory_inside_an_enabled_call() {
+    // Given
+    let program = _overwrite_program();
+
+    // When
+    let (circuit_data, witness_target_map, inlined_calls, _) =
+        utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    //Then
+    let proof = utils::generate_plonky2_proof_using_witness_values_of_each_function(
+        vec![
+            (
+                _field_values(vec![(0, 3), (1, 7), (2, 1)]),
+                &witness_target_map,
+            ),
+            (
+                _field_values(vec![(1, 7)]),
+                &inlined_calls[0].witness_target_map,
+            ),
+        ],
+        &circuit_data,
+    );
+    assert_eq!(F::from_canonical_u64(7), proof.public_inputs[0]);
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_plonky2_backend_does_not_write_to_memory_inside_a_disabled_call() {
+    // Given
+    let program = _overwrite_program();
+
+    // When
+    let (circuit_data, witness_target_map, inlined_calls, _) =
+        utils::generate_plonky2_circuit_from_acir_program(&program);
+
+    //Then
+    // The prover gives zero to every witness of a disabled call, which the write of 7 would
+    // contradict if it was not disabled along with the call
+    let proof = utils::generate_plonky2_proof_using_witness_values_of_each_function(
+        vec![
+            (
+                _field_values(vec![(0, 3), (1, 0), (2, 0)]),
+                &witness_target_map,
+            ),
+            (
+                _field_values(vec![(1, 0)]),
+                &inlined_calls[0].witness_target_map,
+            ),
+        ],
+        &circuit_data,
+    );
+    assert_eq!(F::ZERO, proof.public_inputs[0]);
+    assert!(circuit_data.verify(proof).is_ok());
+}
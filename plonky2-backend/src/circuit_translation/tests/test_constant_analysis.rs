@@ -0,0 +1,159 @@
+use super::factories::circuit_factory::*;
+use super::factories::utils;
+use super::*;
+use acir::circuit::brillig::BrilligInputs;
+
+// x + c = 0
+fn _x_plus_constant_equals_0_opcode(
+    public_input_witness: Witness,
+    constant: FieldElement,
+) -> Opcode {
+    Opcode::AssertZero(Expression {
+        mul_terms: Vec::new(),
+        linear_combinations: vec![(FieldElement::one(), public_input_witness)],
+        q_c: constant,
+    })
+}
+
+#[test]
+fn test_bn254_negative_constants_are_goldilocks_negative_constants() {
+    // Given
+    let public_input_witness = Witness(0);
+    let only_opcode = _x_plus_constant_equals_0_opcode(public_input_witness, -FieldElement::one());
+    let circuit = circuit_with_single_opcode(only_opcode, vec![public_input_witness]);
+
+    // When
+    let (circuit_data, witness_target_map) =
+        utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let one = F::from_canonical_u64(1);
+    let proof = utils::generate_plonky2_proof_using_witness_values(
+        vec![(public_input_witness, one)],
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_bn254_negative_coefficients_are_goldilocks_negative_coefficients() {
+    // Given
+    let public_input_witness = Witness(0);
+    let only_opcode = Opcode::AssertZero(Expression {
+        mul_terms: vec![(
+            -FieldElement::from(2u128),
+            public_input_witness,
+            public_input_witness,
+        )],
+        linear_combinations: vec![],
+        q_c: FieldElement::from(18u128),
+    });
+    let circuit = circuit_with_single_opcode(only_opcode, vec![public_input_witness]);
+
+    // When
+    let (circuit_data, witness_target_map) =
+        utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let three = F::from_canonical_u64(3);
+    let proof = utils::generate_plonky2_proof_using_witness_values(
+        vec![(public_input_witness, three)],
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "found in opcodes [1]")]
+fn test_constants_that_do_not_fit_in_goldilocks_are_rejected() {
+    // Given
+    let public_input_witness = Witness(0);
+    let big_constant = FieldElement::from(1u128 << 100);
+    let mut circuit = circuit_with_single_opcode(
+        x_equals_0_opcode(public_input_witness),
+        vec![public_input_witness],
+    );
+    circuit.opcodes.push(_x_plus_constant_equals_0_opcode(
+        public_input_witness,
+        big_constant,
+    ));
+
+    // When
+    utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+}
+
+#[test]
+#[should_panic(expected = "found in opcodes [0]")]
+fn test_coefficients_that_do_not_fit_in_goldilocks_are_rejected() {
+    // Given
+    let public_input_witness = Witness(0);
+    let big_coefficient = -FieldElement::from(1u128 << 100);
+    let only_opcode = Opcode::AssertZero(Expression {
+        mul_terms: vec![],
+        linear_combinations: vec![(big_coefficient, public_input_witness)],
+        q_c: FieldElement::zero(),
+    });
+    let circuit = circuit_with_single_opcode(only_opcode, vec![public_input_witness]);
+
+    // When
+    utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+}
+
+#[test]
+#[should_panic(expected = "found in opcodes [0]")]
+fn test_call_predicates_that_do_not_fit_in_goldilocks_are_rejected() {
+    // Given
+    let only_opcode = Opcode::Call {
+        id: 1,
+        inputs: vec![Witness(0)],
+        outputs: vec![],
+        predicate: Some(Expression::from_field(FieldElement::from(1u128 << 100))),
+    };
+    let circuit = circuit_with_single_opcode(only_opcode, vec![]);
+
+    // When
+    utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+}
+
+#[test]
+#[should_panic(expected = "found in opcodes [1]")]
+fn test_brillig_inputs_that_do_not_fit_in_goldilocks_are_rejected() {
+    // Given
+    let public_input_witness = Witness(0);
+    let mut circuit = circuit_with_single_opcode(
+        x_equals_0_opcode(public_input_witness),
+        vec![public_input_witness],
+    );
+    circuit.opcodes.push(Opcode::BrilligCall {
+        id: 0,
+        inputs: vec![
+            BrilligInputs::Single(Expression::from(public_input_witness)),
+            BrilligInputs::Array(vec![Expression::from_field(FieldElement::from(
+                1u128 << 100,
+            ))]),
+        ],
+        outputs: vec![],
+        predicate: None,
+    });
+
+    // When
+    utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+}
+
+#[test]
+#[should_panic(expected = "found in opcodes [0]")]
+fn test_brillig_predicates_that_do_not_fit_in_goldilocks_are_rejected() {
+    // Given
+    let only_opcode = Opcode::BrilligCall {
+        id: 0,
+        inputs: vec![],
+        outputs: vec![],
+        predicate: Some(Expression::from_field(-FieldElement::from(1u128 << 100))),
+    };
+    let circuit = circuit_with_single_opcode(only_opcode, vec![]);
+
+    // When
+    utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+}
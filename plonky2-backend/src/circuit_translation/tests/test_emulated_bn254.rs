@@ -0,0 +1,328 @@
+use super::factories::circuit_factory::*;
+use super::factories::utils;
+use super::*;
+use crate::circuit_translation::emulated_bn254_translator::{
+    bn254_limbs_of, check_emulated_bn254_support, emulated_bn254_support,
+    UnsupportedInEmulatedBn254Mode,
+};
+use acir::circuit::opcodes::{BlockId, BlockType};
+
+// fn main(x: Field, y: Field) -> pub Field { y / x }
+// The quotient is computed by Brillig, so the circuit only checks x * w2 - y = 0
+fn _division_circuit() -> Circuit {
+    let x = Witness(0);
+    let y = Witness(1);
+    let quotient = Witness(2);
+    let only_opcode = Opcode::AssertZero(Expression {
+        mul_terms: vec![(FieldElement::one(), x, quotient)],
+        linear_combinations: vec![(-FieldElement::one(), y)],
+        q_c: FieldElement::zero(),
+    });
+    circuit_with_single_opcode(only_opcode, vec![])
+}
+
+fn _range_check_circuit(num_bits: u32) -> Circuit {
+    let only_opcode = Opcode::BlackBoxFuncCall(opcodes::BlackBoxFuncCall::RANGE {
+        input: FunctionInput {
+            witness: Witness(0),
+            num_bits,
+        },
+    });
+    circuit_with_single_opcode(only_opcode, vec![])
+}
+
+#[test]
+fn test_emulated_bn254_division_agrees_with_the_acvm() {
+    // Given
+    let circuit = _division_circuit();
+    let (circuit_data, witness_target_map) =
+        utils::generate_emulated_bn254_circuit_from_acir_circuit(&circuit);
+
+    // When
+    let two = FieldElement::from(2u128);
+    let half = two.inverse();
+    let proof = utils::generate_emulated_bn254_proof_using_witness_values(
+        vec![
+            (Witness(0), two),
+            (Witness(1), FieldElement::one()),
+            (Witness(2), half),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+
+    //Then
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_emulated_bn254_division_rejects_the_goldilocks_inverse() {
+    // Given
+    let circuit = _division_circuit();
+    let (circuit_data, witness_target_map) =
+        utils::generate_emulated_bn254_circuit_from_acir_circuit(&circuit);
+
+    // When
+    let goldilocks_half = FieldElement::from(0x7fffffff80000001u128);
+    utils::generate_emulated_bn254_proof_using_witness_values(
+        vec![
+            (Witness(0), FieldElement::from(2u128)),
+            (Witness(1), FieldElement::one()),
+            (Witness(2), goldilocks_half),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+#[test]
+fn test_emulated_bn254_public_input_can_be_a_negative_number() {
+    // Given
+    let public_input_witness = Witness(0);
+    let only_opcode = Opcode::AssertZero(Expression {
+        mul_terms: vec![],
+        linear_combinations: vec![(FieldElement::one(), public_input_witness)],
+        q_c: FieldElement::one(),
+    });
+    let circuit = circuit_with_single_opcode(only_opcode, vec![public_input_witness]);
+    let (circuit_data, witness_target_map) =
+        utils::generate_emulated_bn254_circuit_from_acir_circuit(&circuit);
+
+    // When
+    let minus_one = -FieldElement::one();
+    let proof = utils::generate_emulated_bn254_proof_using_witness_values(
+        vec![(public_input_witness, minus_one)],
+        &witness_target_map,
+        &circuit_data,
+    );
+
+    //Then
+    assert_eq!(bn254_limbs_of(&minus_one), proof.public_inputs);
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_emulated_bn254_range_check_accepts_values_above_the_goldilocks_modulus() {
+    // Given
+    let circuit = _range_check_circuit(128);
+    let (circuit_data, witness_target_map) =
+        utils::generate_emulated_bn254_circuit_from_acir_circuit(&circuit);
+
+    // When
+    let value = FieldElement::from(1u128 << 100);
+    let proof = utils::generate_emulated_bn254_proof_using_witness_values(
+        vec![(Witness(0), value)],
+        &witness_target_map,
+        &circuit_data,
+    );
+
+    //Then
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_emulated_bn254_range_check_fails_for_a_value_with_too_many_bits() {
+    // Given
+    let circuit = _range_check_circuit(70);
+    let (circuit_data, witness_target_map) =
+        utils::generate_emulated_bn254_circuit_from_acir_circuit(&circuit);
+
+    // When
+    let value = FieldElement::from(1u128 << 70);
+    utils::generate_emulated_bn254_proof_using_witness_values(
+        vec![(Witness(0), value)],
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+#[test]
+fn test_emulated_bn254_u128_range_check_accepts_the_max_u128() {
+    // Given
+    let circuit = _range_check_circuit(128);
+    let (circuit_data, witness_target_map) =
+        utils::generate_emulated_bn254_circuit_from_acir_circuit(&circuit);
+
+    // When
+    let proof = utils::generate_emulated_bn254_proof_using_witness_values(
+        vec![(Witness(0), FieldElement::from(u128::MAX))],
+        &witness_target_map,
+        &circuit_data,
+    );
+
+    //Then
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_emulated_bn254_u128_range_check_fails_for_2_to_the_128() {
+    // Given
+    let circuit = _range_check_circuit(128);
+    let (circuit_data, witness_target_map) =
+        utils::generate_emulated_bn254_circuit_from_acir_circuit(&circuit);
+
+    // When
+    let value = FieldElement::from(u128::MAX) + FieldElement::one();
+    utils::generate_emulated_bn254_proof_using_witness_values(
+        vec![(Witness(0), value)],
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_emulated_bn254_u64_range_check_fails_for_2_to_the_64() {
+    // Given
+    let circuit = _range_check_circuit(64);
+    let (circuit_data, witness_target_map) =
+        utils::generate_emulated_bn254_circuit_from_acir_circuit(&circuit);
+
+    // When
+    utils::generate_emulated_bn254_proof_using_witness_values(
+        vec![(Witness(0), FieldElement::from(1u128 << 64))],
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+#[test]
+fn test_emulated_bn254_xor_of_20_bit_numbers() {
+    // Given
+    let only_opcode = Opcode::BlackBoxFuncCall(opcodes::BlackBoxFuncCall::XOR {
+        lhs: FunctionInput {
+            witness: Witness(0),
+            num_bits: 20,
+        },
+        rhs: FunctionInput {
+            witness: Witness(1),
+            num_bits: 20,
+        },
+        output: Witness(2),
+    });
+    let circuit = circuit_with_single_opcode(only_opcode, vec![]);
+    let (circuit_data, witness_target_map) =
+        utils::generate_emulated_bn254_circuit_from_acir_circuit(&circuit);
+
+    // When
+    let proof = utils::generate_emulated_bn254_proof_using_witness_values(
+        vec![
+            (Witness(0), FieldElement::from(0xf0f0fu128)),
+            (Witness(1), FieldElement::from(0x12345u128)),
+            (Witness(2), FieldElement::from((0xf0f0f ^ 0x12345) as u128)),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+
+    //Then
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+fn _program_with_main(main: Circuit) -> Program {
+    Program {
+        functions: vec![main],
+        unconstrained_functions: vec![],
+    }
+}
+
+#[test]
+fn test_emulated_bn254_mode_supports_arithmetic_range_and_bitwise_opcodes() {
+    // Given
+    let program = _program_with_main(bitwise_and_circuit(Witness(0), Witness(1), Witness(2), 8));
+
+    // When
+    check_emulated_bn254_support(&program);
+}
+
+#[test]
+#[should_panic(
+    expected = "The MemoryInit opcode is unsupported in --emulate-bn254 mode, which only supports arithmetic, range, AND and XOR opcodes"
+)]
+fn test_emulated_bn254_mode_rejects_memory_opcodes() {
+    // Given
+    let program = _program_with_main(circuit_with_single_opcode(
+        Opcode::MemoryInit {
+            block_id: BlockId(0),
+            init: vec![Witness(0)],
+            block_type: BlockType::Memory,
+        },
+        vec![],
+    ));
+
+    // When
+    check_emulated_bn254_support(&program);
+}
+
+#[test]
+fn test_emulated_bn254_mode_rejects_sha256() {
+    // Given
+    let output_witnesses: [Witness; 32] =
+        (1..33).map(Witness).collect::<Vec<_>>().try_into().unwrap();
+    let program = _program_with_main(sha256_circuit_with_inputs(
+        vec![Witness(0)],
+        output_witnesses,
+    ));
+
+    // When
+    let result = emulated_bn254_support(&program);
+
+    //Then
+    assert_eq!(
+        Err(UnsupportedInEmulatedBn254Mode::Opcode("SHA256".to_string())),
+        result
+    );
+}
+
+fn _program_calling_a_second_function(main: Circuit) -> Program {
+    let mut program = _program_with_main(main);
+    program.functions.push(circuit_with_single_opcode(
+        x_equals_0_opcode(Witness(0)),
+        vec![],
+    ));
+    program
+}
+
+#[test]
+fn test_emulated_bn254_mode_rejects_function_calls() {
+    // Given
+    let program = _program_calling_a_second_function(circuit_with_single_opcode(
+        Opcode::Call {
+            id: 1,
+            inputs: vec![Witness(0)],
+            outputs: vec![Witness(1)],
+            predicate: None,
+        },
+        vec![],
+    ));
+
+    // When
+    let result = emulated_bn254_support(&program);
+
+    //Then
+    assert_eq!(
+        Err(UnsupportedInEmulatedBn254Mode::Opcode("Call".to_string())),
+        result
+    );
+}
+
+#[test]
+fn test_emulated_bn254_mode_rejects_programs_with_more_than_one_function() {
+    // Given
+    let program = _program_calling_a_second_function(circuit_with_single_opcode(
+        x_equals_0_opcode(Witness(0)),
+        vec![],
+    ));
+
+    // When
+    let result = emulated_bn254_support(&program);
+
+    //Then
+    assert_eq!(
+        Err(UnsupportedInEmulatedBn254Mode::MultipleFunctions),
+        result
+    );
+}
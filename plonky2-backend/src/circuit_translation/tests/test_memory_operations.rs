@@ -22,14 +22,10 @@ fn test_plonky2_backend_can_translate_a_program_with_basic_memory_operations() {
 
     //Then
     let zero = F::from_canonical_u64(0);
-    let one = F::from_canonical_u64(1);
     let proof = generate_plonky2_proof_using_witness_values(
         vec![
             (array_only_position_input_witness, zero),
             (index_input_witness, zero),
-            (Witness(2), one),
-            (Witness(3), zero),
-            (Witness(4), one),
         ],
         &witness_target_map,
         &circuit_data,
@@ -37,6 +33,792 @@ fn test_plonky2_backend_can_translate_a_program_with_basic_memory_operations() {
     assert!(circuit_data.verify(proof).is_ok());
 }
 
+#[test]
+fn test_plonky2_backend_can_translate_a_read_from_a_read_only_array() {
+    /*fn main(x: [Field; 3], y: Field) -> pub Field {
+        x[y]
+    }*/
+
+    // Given
+    let array_witnesses = vec![Witness(0), Witness(1), Witness(2)];
+    let index_witness = Witness(3);
+    let read_value_witness = Witness(4);
+    let circuit =
+        _read_only_memory_circuit(array_witnesses.clone(), index_witness, read_value_witness);
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let proof = generate_plonky2_proof_using_witness_values(
+        vec![
+            (array_witnesses[0], F::from_canonical_u64(10)),
+            (array_witnesses[1], F::from_canonical_u64(20)),
+            (array_witnesses[2], F::from_canonical_u64(30)),
+            (index_witness, F::from_canonical_u64(2)),
+            (read_value_witness, F::from_canonical_u64(30)),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_plonky2_backend_cannot_read_a_value_that_is_not_stored_in_the_array() {
+    // Given
+    let array_witnesses = vec![Witness(0), Witness(1), Witness(2)];
+    let index_witness = Witness(3);
+    let read_value_witness = Witness(4);
+    let circuit =
+        _read_only_memory_circuit(array_witnesses.clone(), index_witness, read_value_witness);
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    generate_plonky2_proof_using_witness_values(
+        vec![
+            (array_witnesses[0], F::from_canonical_u64(10)),
+            (array_witnesses[1], F::from_canonical_u64(20)),
+            (array_witnesses[2], F::from_canonical_u64(30)),
+            (index_witness, F::from_canonical_u64(2)),
+            (read_value_witness, F::from_canonical_u64(20)),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_plonky2_backend_cannot_read_out_of_the_array_bounds() {
+    // Given
+    let array_witnesses = vec![Witness(0), Witness(1), Witness(2)];
+    let index_witness = Witness(3);
+    let read_value_witness = Witness(4);
+    let circuit =
+        _read_only_memory_circuit(array_witnesses.clone(), index_witness, read_value_witness);
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    generate_plonky2_proof_using_witness_values(
+        vec![
+            (array_witnesses[0], F::from_canonical_u64(10)),
+            (array_witnesses[1], F::from_canonical_u64(20)),
+            (array_witnesses[2], F::from_canonical_u64(30)),
+            (index_witness, F::from_canonical_u64(3)),
+            (read_value_witness, F::from_canonical_u64(0)),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+#[test]
+fn test_plonky2_backend_can_read_from_a_read_only_array_with_more_than_64_elements() {
+    // Given
+    let array_witnesses: Vec<Witness> = (0..100).map(Witness).collect();
+    let index_witness = Witness(100);
+    let read_value_witness = Witness(101);
+    let circuit =
+        _read_only_memory_circuit(array_witnesses.clone(), index_witness, read_value_witness);
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let mut witness_assignment: Vec<(Witness, F)> = array_witnesses
+        .iter()
+        .map(|witness| (*witness, F::from_canonical_u32(witness.0 * 2)))
+        .collect();
+    witness_assignment.push((index_witness, F::from_canonical_u64(77)));
+    witness_assignment.push((read_value_witness, F::from_canonical_u64(154)));
+
+    let proof = generate_plonky2_proof_using_witness_values(
+        witness_assignment,
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+fn _read_only_memory_circuit(
+    array_witnesses: Vec<Witness>,
+    index_witness: Witness,
+    read_value_witness: Witness,
+) -> Circuit {
+    /*
+    INIT (id: 0, len: n)
+    MEM (id: 0, read at: x_index, value: x_value)
+    */
+    Circuit {
+        current_witness_index: 0,
+        expression_width: ExpressionWidth::Unbounded,
+        opcodes: vec![
+            Opcode::MemoryInit {
+                block_id: BlockId(0),
+                init: array_witnesses.clone(),
+                block_type: Memory,
+            },
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp {
+                    operation: expression_zero(),
+                    index: expression_witness(index_witness),
+                    value: expression_witness(read_value_witness),
+                },
+                predicate: None,
+            },
+        ],
+        private_parameters: BTreeSet::from_iter(
+            array_witnesses.into_iter().chain(vec![index_witness]),
+        ),
+        public_parameters: PublicInputs(BTreeSet::new()),
+        return_values: PublicInputs(BTreeSet::from_iter(vec![read_value_witness])),
+        assert_messages: Default::default(),
+        recursive: false,
+    }
+}
+
+#[test]
+fn test_plonky2_backend_can_translate_an_empty_memory_block_that_is_never_accessed() {
+    /*fn main(x: [Field; 0]) {}*/
+
+    // Given
+    let circuit = _empty_memory_circuit(vec![]);
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let proof =
+        generate_plonky2_proof_using_witness_values(vec![], &witness_target_map, &circuit_data);
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Cannot access an empty memory block")]
+fn test_plonky2_backend_cannot_write_into_an_empty_memory_block() {
+    // Given
+    let circuit = _empty_memory_circuit(vec![Opcode::MemoryOp {
+        block_id: BlockId(0),
+        op: MemOp {
+            operation: expression_one(),
+            index: expression_zero(),
+            value: expression_witness(Witness(0)),
+        },
+        predicate: None,
+    }]);
+
+    // When
+    generate_plonky2_circuit_from_acir_circuit(&circuit);
+}
+
+fn _empty_memory_circuit(memory_operations: Vec<Opcode>) -> Circuit {
+    /*
+    INIT (id: 0, len: 0)
+    */
+    let mut opcodes = vec![Opcode::MemoryInit {
+        block_id: BlockId(0),
+        init: vec![],
+        block_type: Memory,
+    }];
+    opcodes.extend(memory_operations);
+    Circuit {
+        current_witness_index: 0,
+        expression_width: ExpressionWidth::Unbounded,
+        opcodes,
+        private_parameters: BTreeSet::new(),
+        public_parameters: PublicInputs(BTreeSet::new()),
+        return_values: PublicInputs(BTreeSet::new()),
+        assert_messages: Default::default(),
+        recursive: false,
+    }
+}
+
+#[test]
+fn test_plonky2_backend_can_read_the_last_value_written_at_an_index() {
+    /*fn main(mut x: [Field; 2], y: Field, z: Field, i: Field) -> pub Field {
+        x[i] = z;
+        x[y]
+    }*/
+
+    // Given
+    let circuit = _write_then_read_circuit();
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let proof = generate_plonky2_proof_using_witness_values(
+        _write_then_read_witness_values(1, 1, 7),
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_plonky2_backend_can_read_an_initial_value_not_overwritten() {
+    // Given
+    let circuit = _write_then_read_circuit();
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let proof = generate_plonky2_proof_using_witness_values(
+        _write_then_read_witness_values(1, 0, 10),
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_plonky2_backend_cannot_read_an_overwritten_value() {
+    // Given
+    let circuit = _write_then_read_circuit();
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    generate_plonky2_proof_using_witness_values(
+        _write_then_read_witness_values(1, 1, 20),
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_plonky2_backend_cannot_write_out_of_the_array_bounds() {
+    // Given
+    let circuit = _write_then_read_circuit();
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    generate_plonky2_proof_using_witness_values(
+        _write_then_read_witness_values(2, 0, 10),
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+#[test]
+fn test_plonky2_backend_can_read_at_an_index_read_from_the_same_array() {
+    /*fn main(mut x: [Field; 2], z: Field) -> pub Field {
+        x[1] = z;
+        x[x[0]]
+    }*/
+
+    // Given
+    let circuit = _circuit_with_memory_operations(
+        2,
+        vec![
+            MemOp {
+                operation: expression_one(),
+                index: expression_constant(1),
+                value: expression_witness(Witness(2)),
+            },
+            MemOp {
+                operation: expression_zero(),
+                index: expression_constant(0),
+                value: expression_witness(Witness(3)),
+            },
+            MemOp {
+                operation: expression_zero(),
+                index: expression_witness(Witness(3)),
+                value: expression_witness(Witness(4)),
+            },
+        ],
+    );
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let proof = generate_plonky2_proof_using_witness_values(
+        vec![
+            (Witness(0), F::from_canonical_u64(1)),
+            (Witness(1), F::from_canonical_u64(20)),
+            (Witness(2), F::from_canonical_u64(7)),
+            (Witness(3), F::from_canonical_u64(1)),
+            (Witness(4), F::from_canonical_u64(7)),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_plonky2_backend_replays_a_memory_block_with_a_single_generator() {
+    // Given
+    let read_at = |index: u128, value: u32| MemOp {
+        operation: expression_zero(),
+        index: expression_constant(index),
+        value: expression_witness(Witness(value)),
+    };
+    let circuit = _circuit_with_memory_operations(
+        2,
+        vec![
+            MemOp {
+                operation: expression_one(),
+                index: expression_constant(0),
+                value: expression_witness(Witness(2)),
+            },
+            read_at(0, 3),
+            read_at(1, 4),
+            read_at(0, 5),
+        ],
+    );
+
+    // When
+    let (circuit_data, _) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let memory_state_generators = circuit_data
+        .prover_only
+        .generators
+        .iter()
+        .filter(|generator| generator.0.id() == "MemoryStateGenerator")
+        .count();
+    assert_eq!(1, memory_state_generators);
+}
+
+fn _write_then_read_witness_values(
+    write_index: u64,
+    read_index: u64,
+    read_value: u64,
+) -> Vec<(Witness, F)> {
+    vec![
+        (Witness(0), F::from_canonical_u64(10)),
+        (Witness(1), F::from_canonical_u64(20)),
+        (Witness(2), F::from_canonical_u64(read_index)),
+        (Witness(3), F::from_canonical_u64(7)),
+        (Witness(4), F::from_canonical_u64(write_index)),
+        (Witness(5), F::from_canonical_u64(read_value)),
+    ]
+}
+
+fn _write_then_read_circuit() -> Circuit {
+    /*
+    INIT (id: 0, len: 2)
+    MEM (id: 0, write x3 at: x4)
+    MEM (id: 0, read at: x2, value: x5)
+    */
+    Circuit {
+        current_witness_index: 0,
+        expression_width: ExpressionWidth::Unbounded,
+        opcodes: vec![
+            Opcode::MemoryInit {
+                block_id: BlockId(0),
+                init: vec![Witness(0), Witness(1)],
+                block_type: Memory,
+            },
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp {
+                    operation: expression_one(),
+                    index: expression_witness(Witness(4)),
+                    value: expression_witness(Witness(3)),
+                },
+                predicate: None,
+            },
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp {
+                    operation: expression_zero(),
+                    index: expression_witness(Witness(2)),
+                    value: expression_witness(Witness(5)),
+                },
+                predicate: None,
+            },
+        ],
+        private_parameters: BTreeSet::from_iter(vec![
+            Witness(0),
+            Witness(1),
+            Witness(2),
+            Witness(3),
+            Witness(4),
+        ]),
+        public_parameters: PublicInputs(BTreeSet::new()),
+        return_values: PublicInputs(BTreeSet::from_iter(vec![Witness(5)])),
+        assert_messages: Default::default(),
+        recursive: false,
+    }
+}
+
+#[test]
+fn test_plonky2_backend_can_read_from_a_read_only_array_with_a_true_predicate() {
+    // Given
+    let circuit = _predicated_read_circuit();
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let proof = generate_plonky2_proof_using_witness_values(
+        _predicated_read_witness_values(1, 1, 20),
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_plonky2_backend_ignores_the_index_of_a_read_with_a_false_predicate() {
+    // Given
+    let circuit = _predicated_read_circuit();
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let proof = generate_plonky2_proof_using_witness_values(
+        _predicated_read_witness_values(0, 5, 0),
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_plonky2_backend_cannot_read_a_non_zero_value_with_a_false_predicate() {
+    // Given
+    let circuit = _predicated_read_circuit();
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    generate_plonky2_proof_using_witness_values(
+        _predicated_read_witness_values(0, 1, 20),
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+#[test]
+fn test_plonky2_backend_can_write_with_a_true_predicate() {
+    /*fn main(mut x: [Field; 2], y: Field, z: Field, condition: bool) -> pub Field {
+        if condition {
+            x[y] = z;
+        }
+        x[0]
+    }*/
+
+    // Given
+    let circuit = _predicated_write_circuit();
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let proof = generate_plonky2_proof_using_witness_values(
+        _predicated_write_witness_values(1, 7),
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_plonky2_backend_does_not_write_with_a_false_predicate() {
+    // Given
+    let circuit = _predicated_write_circuit();
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let proof = generate_plonky2_proof_using_witness_values(
+        _predicated_write_witness_values(0, 10),
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_plonky2_backend_cannot_read_a_value_written_with_a_false_predicate() {
+    // Given
+    let circuit = _predicated_write_circuit();
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    generate_plonky2_proof_using_witness_values(
+        _predicated_write_witness_values(0, 7),
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+fn _predicated_read_witness_values(
+    predicate: u64,
+    read_index: u64,
+    read_value: u64,
+) -> Vec<(Witness, F)> {
+    vec![
+        (Witness(0), F::from_canonical_u64(10)),
+        (Witness(1), F::from_canonical_u64(20)),
+        (Witness(2), F::from_canonical_u64(read_index)),
+        (Witness(3), F::from_canonical_u64(read_value)),
+        (Witness(4), F::from_canonical_u64(predicate)),
+    ]
+}
+
+fn _predicated_read_circuit() -> Circuit {
+    /*
+    INIT (id: 0, len: 2)
+    MEM PREDICATE = [ (1, _4) 0 ] (id: 0, read at: x2, value: x3)
+    */
+    Circuit {
+        current_witness_index: 0,
+        expression_width: ExpressionWidth::Unbounded,
+        opcodes: vec![
+            Opcode::MemoryInit {
+                block_id: BlockId(0),
+                init: vec![Witness(0), Witness(1)],
+                block_type: Memory,
+            },
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp {
+                    operation: expression_zero(),
+                    index: expression_witness(Witness(2)),
+                    value: expression_witness(Witness(3)),
+                },
+                predicate: Some(expression_witness(Witness(4))),
+            },
+        ],
+        private_parameters: BTreeSet::from_iter(vec![
+            Witness(0),
+            Witness(1),
+            Witness(2),
+            Witness(4),
+        ]),
+        public_parameters: PublicInputs(BTreeSet::new()),
+        return_values: PublicInputs(BTreeSet::from_iter(vec![Witness(3)])),
+        assert_messages: Default::default(),
+        recursive: false,
+    }
+}
+
+fn _predicated_write_witness_values(predicate: u64, read_value: u64) -> Vec<(Witness, F)> {
+    vec![
+        (Witness(0), F::from_canonical_u64(10)),
+        (Witness(1), F::from_canonical_u64(20)),
+        (Witness(2), F::from_canonical_u64(0)),
+        (Witness(3), F::from_canonical_u64(7)),
+        (Witness(4), F::from_canonical_u64(predicate)),
+        (Witness(5), F::from_canonical_u64(0)),
+        (Witness(6), F::from_canonical_u64(read_value)),
+    ]
+}
+
+fn _predicated_write_circuit() -> Circuit {
+    /*
+    INIT (id: 0, len: 2)
+    MEM PREDICATE = [ (1, _4) 0 ] (id: 0, write x3 at: x2)
+    MEM (id: 0, read at: x5, value: x6)
+    */
+    Circuit {
+        current_witness_index: 0,
+        expression_width: ExpressionWidth::Unbounded,
+        opcodes: vec![
+            Opcode::MemoryInit {
+                block_id: BlockId(0),
+                init: vec![Witness(0), Witness(1)],
+                block_type: Memory,
+            },
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp {
+                    operation: expression_one(),
+                    index: expression_witness(Witness(2)),
+                    value: expression_witness(Witness(3)),
+                },
+                predicate: Some(expression_witness(Witness(4))),
+            },
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp {
+                    operation: expression_zero(),
+                    index: expression_witness(Witness(5)),
+                    value: expression_witness(Witness(6)),
+                },
+                predicate: None,
+            },
+        ],
+        private_parameters: BTreeSet::from_iter(vec![
+            Witness(0),
+            Witness(1),
+            Witness(2),
+            Witness(3),
+            Witness(4),
+            Witness(5),
+        ]),
+        public_parameters: PublicInputs(BTreeSet::new()),
+        return_values: PublicInputs(BTreeSet::from_iter(vec![Witness(6)])),
+        assert_messages: Default::default(),
+        recursive: false,
+    }
+}
+
+#[test]
+fn test_plonky2_backend_can_read_at_a_constant_index() {
+    /*fn main(x: [Field; 3]) -> pub Field {
+        x[2]
+    }*/
+
+    // Given
+    let circuit = _circuit_with_memory_operations(
+        3,
+        vec![MemOp {
+            operation: expression_zero(),
+            index: expression_constant(2),
+            value: expression_witness(Witness(3)),
+        }],
+    );
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let proof = generate_plonky2_proof_using_witness_values(
+        vec![
+            (Witness(0), F::from_canonical_u64(10)),
+            (Witness(1), F::from_canonical_u64(20)),
+            (Witness(2), F::from_canonical_u64(30)),
+            (Witness(3), F::from_canonical_u64(30)),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_plonky2_backend_can_write_a_constant_at_a_computed_index() {
+    /*fn main(mut x: [Field; 3], y: Field) -> pub Field {
+        x[2 * y + 1] = 5;
+        x[1]
+    }*/
+
+    // Given
+    let computed_index = Expression {
+        mul_terms: Vec::new(),
+        linear_combinations: vec![(FieldElement::from(2u128), Witness(3))],
+        q_c: FieldElement::one(),
+    };
+    let circuit = _circuit_with_memory_operations(
+        3,
+        vec![
+            MemOp {
+                operation: expression_one(),
+                index: computed_index,
+                value: expression_constant(5),
+            },
+            MemOp {
+                operation: expression_zero(),
+                index: expression_constant(1),
+                value: expression_witness(Witness(4)),
+            },
+        ],
+    );
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    let proof = generate_plonky2_proof_using_witness_values(
+        vec![
+            (Witness(0), F::from_canonical_u64(10)),
+            (Witness(1), F::from_canonical_u64(20)),
+            (Witness(2), F::from_canonical_u64(30)),
+            (Witness(3), F::from_canonical_u64(0)),
+            (Witness(4), F::from_canonical_u64(5)),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_plonky2_backend_cannot_ignore_the_coefficients_of_a_computed_index() {
+    // Given
+    let computed_index = Expression {
+        mul_terms: Vec::new(),
+        linear_combinations: vec![(FieldElement::from(2u128), Witness(3))],
+        q_c: FieldElement::zero(),
+    };
+    let circuit = _circuit_with_memory_operations(
+        3,
+        vec![MemOp {
+            operation: expression_zero(),
+            index: computed_index,
+            value: expression_witness(Witness(4)),
+        }],
+    );
+
+    // When
+    let (circuit_data, witness_target_map) = generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    // Then
+    generate_plonky2_proof_using_witness_values(
+        vec![
+            (Witness(0), F::from_canonical_u64(10)),
+            (Witness(1), F::from_canonical_u64(20)),
+            (Witness(2), F::from_canonical_u64(30)),
+            (Witness(3), F::from_canonical_u64(1)),
+            (Witness(4), F::from_canonical_u64(20)),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+fn _circuit_with_memory_operations(array_length: u32, operations: Vec<MemOp>) -> Circuit {
+    let array_witnesses: Vec<Witness> = (0..array_length).map(Witness).collect();
+    let mut opcodes = vec![Opcode::MemoryInit {
+        block_id: BlockId(0),
+        init: array_witnesses.clone(),
+        block_type: Memory,
+    }];
+    opcodes.extend(operations.into_iter().map(|op| Opcode::MemoryOp {
+        block_id: BlockId(0),
+        op,
+        predicate: None,
+    }));
+
+    Circuit {
+        current_witness_index: 0,
+        expression_width: ExpressionWidth::Unbounded,
+        opcodes,
+        private_parameters: BTreeSet::from_iter(array_witnesses),
+        public_parameters: PublicInputs(BTreeSet::new()),
+        return_values: PublicInputs(BTreeSet::new()),
+        assert_messages: Default::default(),
+        recursive: false,
+    }
+}
+
 fn _memory_opcodes_circuit(
     array_only_position_input_witness: Witness,
     index_input_witness: Witness,
@@ -126,3 +908,11 @@ fn expression_witness(witness: Witness) -> Expression {
         q_c: FieldElement::zero(),
     }
 }
+
+fn expression_constant(constant: u128) -> Expression {
+    Expression {
+        mul_terms: Vec::new(),
+        linear_combinations: Vec::new(),
+        q_c: FieldElement::from(constant),
+    }
+}
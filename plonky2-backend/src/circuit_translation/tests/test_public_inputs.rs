@@ -0,0 +1,175 @@
+use super::factories::circuit_factory::*;
+use super::factories::utils;
+use super::*;
+use acir::circuit::PublicInputs;
+use std::collections::BTreeSet;
+
+// fn main(x: pub Field, y: Field) -> pub Field {
+//     x * y
+// }
+fn _product_circuit() -> Circuit {
+    let mut circuit = circuit_with_single_opcode(
+        Opcode::AssertZero(Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(0), Witness(1))],
+            linear_combinations: vec![(-FieldElement::one(), Witness(2))],
+            q_c: FieldElement::zero(),
+        }),
+        vec![Witness(0)],
+    );
+    circuit.return_values = PublicInputs(BTreeSet::from_iter([Witness(2)]));
+    circuit
+}
+
+#[test]
+fn test_return_values_are_public_inputs_after_the_public_parameters() {
+    // Given
+    let circuit = _product_circuit();
+
+    // When
+    let (circuit_data, witness_target_map) =
+        utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    //Then
+    let three = F::from_canonical_u64(3);
+    let four = F::from_canonical_u64(4);
+    let twelve = F::from_canonical_u64(12);
+    let proof = utils::generate_plonky2_proof_using_witness_values(
+        vec![
+            (Witness(0), three),
+            (Witness(1), four),
+            (Witness(2), twelve),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert_eq!(vec![three, twelve], proof.public_inputs);
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_return_values_must_satisfy_the_circuit() {
+    // Given
+    let circuit = _product_circuit();
+
+    // When
+    let (circuit_data, witness_target_map) =
+        utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    //Then
+    utils::generate_plonky2_proof_using_witness_values(
+        vec![
+            (Witness(0), F::from_canonical_u64(3)),
+            (Witness(1), F::from_canonical_u64(4)),
+            (Witness(2), F::from_canonical_u64(13)),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+}
+
+#[test]
+fn test_a_public_parameter_can_also_be_a_return_value() {
+    // fn main(x: pub Field) -> pub Field {
+    //     x
+    // }
+
+    // Given
+    let mut circuit = circuit_with_single_opcode(
+        Opcode::AssertZero(Expression {
+            mul_terms: vec![],
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(0)),
+                (-FieldElement::one(), Witness(1)),
+            ],
+            q_c: FieldElement::zero(),
+        }),
+        vec![Witness(0)],
+    );
+    circuit.return_values = PublicInputs(BTreeSet::from_iter([Witness(0)]));
+
+    // When
+    let (circuit_data, witness_target_map) =
+        utils::generate_plonky2_circuit_from_acir_circuit(&circuit);
+
+    //Then
+    let seven = F::from_canonical_u64(7);
+    let proof = utils::generate_plonky2_proof_using_witness_values(
+        vec![(Witness(0), seven), (Witness(1), seven)],
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert_eq!(vec![seven, seven], proof.public_inputs);
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+// fn main(x: pub Field, y: Field) -> pub (Field, Field) {
+//     (x * y, x + 1)
+// }
+// compiled with the witness of x + 1 before the one of x * y
+fn _tuple_circuit() -> Circuit {
+    let mut circuit = circuit_with_single_opcode(
+        Opcode::AssertZero(Expression {
+            mul_terms: vec![],
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(0)),
+                (-FieldElement::one(), Witness(2)),
+            ],
+            q_c: FieldElement::one(),
+        }),
+        vec![Witness(0)],
+    );
+    circuit.opcodes.push(Opcode::AssertZero(Expression {
+        mul_terms: vec![(FieldElement::one(), Witness(0), Witness(1))],
+        linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+        q_c: FieldElement::zero(),
+    }));
+    circuit.private_parameters = BTreeSet::from([Witness(1)]);
+    circuit.return_values = PublicInputs(BTreeSet::from_iter([Witness(2), Witness(3)]));
+    circuit
+}
+
+#[test]
+fn test_return_values_are_public_inputs_in_the_order_of_the_abi_return_witnesses() {
+    // Given
+    let circuit = _tuple_circuit();
+    let mut translator = CircuitBuilderFromAcirToPlonky2::new();
+    translator.return_witnesses = Some(vec![Witness(3), Witness(2)]);
+
+    // When
+    translator.translate_circuit(&circuit);
+    let (circuit_data, witness_target_map) = translator.unpack();
+
+    //Then
+    let proof = utils::generate_plonky2_proof_using_witness_values(
+        vec![
+            (Witness(0), F::from_canonical_u64(3)),
+            (Witness(1), F::from_canonical_u64(4)),
+        ],
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert_eq!(
+        vec![
+            F::from_canonical_u64(3),
+            F::from_canonical_u64(12),
+            F::from_canonical_u64(4)
+        ],
+        proof.public_inputs
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+#[should_panic(
+    expected = "The return witnesses of the ABI must be the return values of the program"
+)]
+fn test_the_abi_return_witnesses_must_be_the_return_values_of_the_program() {
+    // Given
+    let circuit = _tuple_circuit();
+    let mut translator = CircuitBuilderFromAcirToPlonky2::new();
+    translator.return_witnesses = Some(vec![Witness(3), Witness(1)]);
+
+    // When
+    translator.translate_circuit(&circuit);
+}
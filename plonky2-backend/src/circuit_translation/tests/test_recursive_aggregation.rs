@@ -0,0 +1,190 @@
+use super::factories::plonky2_factory::*;
+use super::*;
+use crate::circuit_translation::recursive_aggregation_translator::*;
+use crate::circuit_translation::tests::factories::circuit_factory;
+use crate::circuit_translation::tests::factories::utils::*;
+use plonky2::plonk::config::{KeccakGoldilocksConfig, PoseidonGoldilocksConfig};
+use plonky2::plonk::proof::ProofWithPublicInputs;
+
+// fn main(x: Field) -> pub Field { x * x }, proven with the Poseidon hash config
+fn _inner_circuit_and_proof(
+    x: u64,
+) -> (
+    CircuitData<F, PoseidonGoldilocksConfig, D>,
+    ProofWithPublicInputs<F, PoseidonGoldilocksConfig, D>,
+) {
+    let square = power_circuit::<PoseidonGoldilocksConfig>(2);
+    let proof = power_proof(&square, x);
+    (square.0, proof)
+}
+
+// fn main(vk: [Field; _], proof: [Field; _], y: pub Field, key_hash: Field) {
+//     std::verify_proof(vk, proof, [y], key_hash);
+// }
+fn _outer_circuit_verifying(
+    verification_key_length: usize,
+    proof_length: usize,
+) -> (Circuit, Vec<Witness>, Vec<Witness>, Witness, Witness) {
+    let verification_key_witnesses: Vec<Witness> =
+        (0..verification_key_length as u32).map(Witness).collect();
+    let proof_witnesses: Vec<Witness> = (0..proof_length as u32)
+        .map(|index| Witness(verification_key_length as u32 + index))
+        .collect();
+    let public_input_witness = Witness((verification_key_length + proof_length) as u32);
+    let key_hash_witness = Witness(public_input_witness.0 + 1);
+    let circuit = circuit_factory::recursive_aggregation_circuit(
+        verification_key_witnesses.clone(),
+        proof_witnesses.clone(),
+        vec![public_input_witness],
+        key_hash_witness,
+    );
+    (
+        circuit,
+        verification_key_witnesses,
+        proof_witnesses,
+        public_input_witness,
+        key_hash_witness,
+    )
+}
+
+fn _translate_with_inner_common_data(
+    circuit: &Circuit,
+    inner_circuit_data: &CircuitData<F, PoseidonGoldilocksConfig, D>,
+) -> (
+    CircuitData<F, KeccakGoldilocksConfig, D>,
+    HashMap<Witness, Target>,
+) {
+    let mut translator = CircuitBuilderFromAcirToPlonky2::new();
+    translator.recursive_common_data = vec![inner_circuit_data.common.clone()];
+    translator.translate_circuit(circuit);
+    translator.unpack()
+}
+
+fn _prove_outer_circuit(x: u64, claimed_public_input: F, claimed_key_hash: Option<F>) {
+    let (inner_circuit_data, inner_proof) = _inner_circuit_and_proof(x);
+    let verification_key = verifier_data_fields(&inner_circuit_data.verifier_only);
+    let inner_proof_fields = proof_fields(&inner_proof.proof);
+    let (
+        circuit,
+        verification_key_witnesses,
+        proof_witnesses,
+        public_input_witness,
+        key_hash_witness,
+    ) = _outer_circuit_verifying(verification_key.len(), inner_proof_fields.len());
+    let (circuit_data, witness_target_map) =
+        _translate_with_inner_common_data(&circuit, &inner_circuit_data);
+
+    let mut witness_assignment: Vec<(Witness, F)> = verification_key_witnesses
+        .into_iter()
+        .zip(verification_key.clone())
+        .collect();
+    witness_assignment.extend(proof_witnesses.into_iter().zip(inner_proof_fields));
+    witness_assignment.push((public_input_witness, claimed_public_input));
+    witness_assignment.push((
+        key_hash_witness,
+        claimed_key_hash.unwrap_or(key_hash(&verification_key)),
+    ));
+    let proof = generate_plonky2_proof_using_witness_values(
+        witness_assignment,
+        &witness_target_map,
+        &circuit_data,
+    );
+
+    assert_eq!(vec![claimed_public_input], proof.public_inputs);
+    assert!(circuit_data.verify(proof).is_ok());
+}
+
+#[test]
+fn test_backend_can_verify_a_plonky2_proof_inside_the_circuit() {
+    _prove_outer_circuit(3, F::from_canonical_u64(9), None);
+}
+
+#[test]
+#[should_panic]
+fn test_backend_cannot_verify_an_inner_proof_with_other_public_inputs() {
+    _prove_outer_circuit(3, F::from_canonical_u64(10), None);
+}
+
+#[test]
+#[should_panic]
+fn test_backend_cannot_verify_an_inner_proof_with_a_wrong_key_hash() {
+    _prove_outer_circuit(3, F::from_canonical_u64(9), Some(F::from_canonical_u64(1)));
+}
+
+#[test]
+#[should_panic(
+    expected = "The inner program expects 68 fields for the verification key, but 67 were given"
+)]
+fn test_backend_rejects_a_verification_key_of_the_wrong_length() {
+    // Given
+    let (inner_circuit_data, inner_proof) = _inner_circuit_and_proof(3);
+    let proof_length = proof_fields(&inner_proof.proof).len();
+    let (circuit, _, _, _, _) = _outer_circuit_verifying(67, proof_length);
+
+    // When
+    _translate_with_inner_common_data(&circuit, &inner_circuit_data);
+}
+
+#[test]
+#[should_panic(expected = "Verifying a proof needs the verifying key of the inner program")]
+fn test_backend_needs_the_inner_verifying_key_to_translate_a_recursive_aggregation() {
+    // Given
+    let (_, inner_proof) = _inner_circuit_and_proof(3);
+    let proof_length = proof_fields(&inner_proof.proof).len();
+    let (circuit, _, _, _, _) = _outer_circuit_verifying(68, proof_length);
+
+    // When
+    generate_plonky2_circuit_from_acir_circuit(&circuit);
+}
+
+// fn main(vk: [Field; _], proof: [Field; _], y: Field, key_hash: Field, enabled: bool) {
+//     if enabled { verify(vk, proof, y, key_hash) }
+// }
+// #[fold]
+// fn verify(vk: [Field; _], proof: [Field; _], y: pub Field, key_hash: Field) {
+//     std::verify_proof(vk, proof, [y], key_hash);
+// }
+#[test]
+fn test_backend_does_not_verify_a_proof_inside_a_disabled_call() {
+    // Given
+    let (inner_circuit_data, inner_proof) = _inner_circuit_and_proof(3);
+    let verification_key_length = verifier_data_fields(&inner_circuit_data.verifier_only).len();
+    let proof_length = proof_fields(&inner_proof.proof).len();
+    let (verify, _, _, _, key_hash_witness) =
+        _outer_circuit_verifying(verification_key_length, proof_length);
+    let inputs: Vec<Witness> = (0..=key_hash_witness.0).map(Witness).collect();
+    let enabled_witness = Witness(key_hash_witness.0 + 1);
+    let main = circuit_factory::circuit_with_single_opcode(
+        Opcode::Call {
+            id: 1,
+            inputs: inputs.clone(),
+            outputs: vec![],
+            predicate: Some(Expression::from(enabled_witness)),
+        },
+        vec![],
+    );
+    let program = Program {
+        functions: vec![main, verify],
+        unconstrained_functions: vec![],
+    };
+    let mut translator = CircuitBuilderFromAcirToPlonky2::new();
+    translator.recursive_common_data = vec![inner_circuit_data.common.clone()];
+
+    // When
+    translator.translate_program(&program);
+    let (circuit_data, witness_target_map, _, _) =
+        translator.unpack_program::<KeccakGoldilocksConfig>();
+
+    //Then
+    let zero_witness_values = inputs
+        .into_iter()
+        .chain([enabled_witness])
+        .map(|witness| (witness, F::ZERO))
+        .collect();
+    let proof = generate_plonky2_proof_using_witness_values(
+        zero_witness_values,
+        &witness_target_map,
+        &circuit_data,
+    );
+    assert!(circuit_data.verify(proof).is_ok());
+}
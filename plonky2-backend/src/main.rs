@@ -9,21 +9,27 @@ use circuit_translation::*;
 use jemallocator::Jemalloc;
 
 use noir_and_plonky2_serialization::*;
+use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2::plonk::circuit_data::VerifierCircuitData;
-use plonky2::plonk::config::{GenericConfig, KeccakGoldilocksConfig};
+use plonky2::plonk::config::GenericConfig;
 use plonky2::plonk::proof::CompressedProofWithPublicInputs;
 use plonky2::util::serialization::DefaultGateSerializer;
 
 use crate::circuit_translation::CircuitBuilderFromAcirToPlonky2;
+use crate::plonky2_config::*;
 
 const D: usize = 2;
 
-type C = KeccakGoldilocksConfig;
-type F = <C as GenericConfig<D>>::F;
+type F = GoldilocksField;
 
 pub mod actions;
 pub mod circuit_translation;
+pub mod noir_abi;
 pub mod noir_and_plonky2_serialization;
+pub mod plonky2_config;
+pub mod proof_aggregation;
+pub mod witness_checker;
+pub mod witness_solver;
 
 #[global_allocator] // This is a plonky2 recommendation
 static GLOBAL: Jemalloc = Jemalloc;
@@ -33,41 +39,170 @@ fn main() {
 
     let command: &str = &args.get(1).expect("Must specify a command");
     match command {
+        "execute" => _execute_execute_command(&args),
         "prove" => _execute_prove_command(&args),
         "write_vk" => _execute_write_vk_command(&args),
+        "write_pk" => _execute_write_pk_command(&args),
         "verify" => _execute_verify_command(&args),
+        "aggregate" => _execute_aggregate_command(&args),
+        "verify_aggregate" => _execute_verify_aggregate_command(&args),
+        "proof_as_fields" => _execute_proof_as_fields_command(&args),
+        "vk_as_fields" => _execute_vk_as_fields_command(&args),
         other => eprintln!("Invalid command: {:?}", other),
     }
 }
 
 fn _execute_prove_command(args: &Vec<String>) {
-    let acir_program_json_path = &args[3];
-    let witness_stack_zip_path = &args[5];
-    let resulting_proof_file_path = &args[7];
     let prove_action = actions::prove_action::ProveAction {
-        acir_program_json_path: acir_program_json_path.clone(),
-        witness_stack_zip_path: witness_stack_zip_path.clone(),
-        resulting_proof_file_path: resulting_proof_file_path.clone(),
+        acir_program_json_path: _required_argument(args, "-c"),
+        witness_stack_zip_path: _optional_argument(args, "-w"),
+        input_values_path: _optional_argument(args, "--inputs"),
+        proving_key_path: _optional_argument(args, "--pk"),
+        resulting_proof_file_path: _required_argument(args, "-o"),
+        emulate_bn254: _has_flag(args, "--emulate-bn254"),
+        hash_config: _hash_config_argument(args),
+        zero_knowledge: !_has_flag(args, "--no-zk"),
+        security_profile: _security_profile_argument(args),
+        recursive_vk_paths: _repeated_argument(args, "--recursive-vk"),
     };
     prove_action.run();
 }
 
+fn _execute_execute_command(args: &Vec<String>) {
+    let execute_action = actions::execute_action::ExecuteAction {
+        acir_program_json_path: _required_argument(args, "-c"),
+        input_values_path: _required_argument(args, "--inputs"),
+        resulting_witness_stack_path: _required_argument(args, "-o"),
+    };
+    execute_action.run()
+}
+
 fn _execute_write_vk_command(args: &Vec<String>) {
     let acir_program_json_path = &args[3];
     let vk_path_output = &args[5];
     let write_vk_action = actions::write_vk_action::WriteVKAction {
         acir_program_json_path: acir_program_json_path.clone(),
         vk_path_output: vk_path_output.clone(),
+        emulate_bn254: _has_flag(args, "--emulate-bn254"),
+        hash_config: _hash_config_argument(args),
+        zero_knowledge: !_has_flag(args, "--no-zk"),
+        security_profile: _security_profile_argument(args),
+        recursive_vk_paths: _repeated_argument(args, "--recursive-vk"),
     };
     write_vk_action.run()
 }
 
+fn _execute_write_pk_command(args: &Vec<String>) {
+    let write_pk_action = actions::write_pk_action::WritePKAction {
+        acir_program_json_path: _required_argument(args, "-c"),
+        pk_path_output: _required_argument(args, "-o"),
+        hash_config: _hash_config_argument(args),
+        zero_knowledge: !_has_flag(args, "--no-zk"),
+        security_profile: _security_profile_argument(args),
+        recursive_vk_paths: _repeated_argument(args, "--recursive-vk"),
+    };
+    write_pk_action.run()
+}
+
 fn _execute_verify_command(args: &Vec<String>) {
     let vk_path = &args[3];
     let proof_path = &args[5];
+    let expected_public_inputs = _optional_argument(args, "-i").map(|public_inputs_path| {
+        actions::verify_action::ExpectedPublicInputs {
+            acir_program_json_path: _optional_argument(args, "-c")
+                .expect("Must specify the program with -c to check its public inputs"),
+            public_inputs_path,
+        }
+    });
     let verify_action = actions::verify_action::VerifyAction {
         proof_path: proof_path.clone(),
         vk_path: vk_path.clone(),
+        expected_public_inputs,
+        min_security_bits: _min_security_argument(args),
     };
     verify_action.run()
 }
+
+fn _execute_aggregate_command(args: &Vec<String>) {
+    let aggregate_action = actions::aggregate_action::AggregateAction {
+        vk_paths: _repeated_argument(args, "-k"),
+        proof_paths: _repeated_argument(args, "-p"),
+        resulting_proof_file_path: _required_argument(args, "-o"),
+        min_security_bits: _min_security_argument(args),
+    };
+    aggregate_action.run()
+}
+
+fn _execute_verify_aggregate_command(args: &Vec<String>) {
+    let verify_aggregate_action = actions::verify_aggregate_action::VerifyAggregateAction {
+        vk_paths: _repeated_argument(args, "-k"),
+        aggregated_proof_path: _required_argument(args, "-p"),
+        min_security_bits: _min_security_argument(args),
+    };
+    verify_aggregate_action.run()
+}
+
+fn _execute_proof_as_fields_command(args: &Vec<String>) {
+    let proof_as_fields_action = actions::proof_as_fields_action::ProofAsFieldsAction {
+        proof_path: _required_argument(args, "-p"),
+        vk_path: _required_argument(args, "-k"),
+        resulting_fields_path: _required_argument(args, "-o"),
+    };
+    proof_as_fields_action.run()
+}
+
+fn _execute_vk_as_fields_command(args: &Vec<String>) {
+    let vk_as_fields_action = actions::vk_as_fields_action::VkAsFieldsAction {
+        vk_path: _required_argument(args, "-k"),
+        resulting_fields_path: _required_argument(args, "-o"),
+    };
+    vk_as_fields_action.run()
+}
+
+// Poseidon is only needed by proofs that get verified inside another plonky2 circuit
+fn _hash_config_argument(args: &Vec<String>) -> HashConfig {
+    _optional_argument(args, "--hash")
+        .map(|name| HashConfig::from_name(&name))
+        .unwrap_or(HashConfig::Keccak)
+}
+
+fn _security_profile_argument(args: &Vec<String>) -> SecurityProfile {
+    _optional_argument(args, "--profile")
+        .map(|name| SecurityProfile::from_name(&name))
+        .unwrap_or(SecurityProfile::Standard100Bit)
+}
+
+fn _min_security_argument(args: &Vec<String>) -> Option<usize> {
+    _optional_argument(args, "--min-security").map(|bits| {
+        bits.parse()
+            .expect("The minimum security must be a number of bits")
+    })
+}
+
+fn _has_flag(args: &Vec<String>, flag: &str) -> bool {
+    args.iter().any(|arg| arg == flag)
+}
+
+fn _required_argument(args: &Vec<String>, flag: &str) -> String {
+    _optional_argument(args, flag).unwrap_or_else(|| panic!("Must specify {}", flag))
+}
+
+fn _optional_argument(args: &Vec<String>, flag: &str) -> Option<String> {
+    args.iter().position(|arg| arg == flag).map(|position| {
+        args.get(position + 1)
+            .expect("Missing value for argument")
+            .clone()
+    })
+}
+
+fn _repeated_argument(args: &Vec<String>, flag: &str) -> Vec<String> {
+    args.iter()
+        .enumerate()
+        .filter(|(_, arg)| *arg == flag)
+        .map(|(position, _)| {
+            args.get(position + 1)
+                .expect("Missing value for argument")
+                .clone()
+        })
+        .collect()
+}
@@ -0,0 +1,289 @@
+use num_bigint::BigUint;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::*;
+
+// Public inputs follow the ABI order: public parameters first and then the return value,
+// each one flattened into field elements the same way nargo lays out their witnesses
+pub fn flatten_public_inputs(abi: &Value, values: &Value) -> Vec<(String, FieldElement)> {
+    let values = _values_by_name(abi, values);
+    let mut flattened_public_inputs = Vec::new();
+    for parameter in _public_parameters(abi) {
+        let name = parameter["name"]
+            .as_str()
+            .expect("ABI parameters must have a name");
+        let value = values
+            .get(name)
+            .unwrap_or_else(|| panic!("Missing value for public parameter `{}`", name));
+        _flatten_value(
+            name.to_string(),
+            &parameter["type"],
+            value,
+            &mut flattened_public_inputs,
+        );
+    }
+    let return_type = &abi["return_type"]["abi_type"];
+    if !return_type.is_null() {
+        let value = values
+            .get("return")
+            .expect("Missing value for the return value of the program");
+        _flatten_value(
+            "return".to_string(),
+            return_type,
+            value,
+            &mut flattened_public_inputs,
+        );
+    }
+    flattened_public_inputs
+}
+
+// nargo assigns consecutive witnesses to the flattened parameters, starting from witness 0
+pub fn initial_witness_from_input_values(abi: &Value, values: &Value) -> WitnessMap {
+    let mut flattened_inputs = Vec::new();
+    for parameter in _parameters(abi) {
+        let name = parameter["name"]
+            .as_str()
+            .expect("ABI parameters must have a name");
+        let value = values
+            .get(name)
+            .unwrap_or_else(|| panic!("Missing value for parameter `{}`", name));
+        _flatten_value(
+            name.to_string(),
+            &parameter["type"],
+            value,
+            &mut flattened_inputs,
+        );
+    }
+    WitnessMap::from(BTreeMap::from_iter(
+        flattened_inputs
+            .into_iter()
+            .enumerate()
+            .map(|(position, (_, value))| (Witness(position as u32), value)),
+    ))
+}
+
+// Only some ABIs list the witnesses of the return value, in the order it is flattened
+pub fn abi_return_witnesses(abi: &Value) -> Option<Vec<Witness>> {
+    let return_witnesses = abi["return_witnesses"].as_array()?;
+    Some(
+        return_witnesses
+            .iter()
+            .map(|witness| {
+                Witness(
+                    witness
+                        .as_u64()
+                        .expect("Return witnesses must be witness indices")
+                        as u32,
+                )
+            })
+            .collect(),
+    )
+}
+
+pub fn read_input_values_within_file_path(input_values_path: &String) -> Value {
+    let mut file = File::open(input_values_path).expect("There was a problem opening the file");
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .expect("There was a problem reading the file content");
+    match input_values_path.ends_with(".json") {
+        true => {
+            serde_json::from_str(&content).expect("There was a problem parsing the json values")
+        }
+        false => parse_toml_input_values(&content),
+    }
+}
+
+pub fn parse_toml_input_values(content: &str) -> Value {
+    let toml_values: toml::Value =
+        toml::from_str(content).expect("There was a problem parsing the toml values");
+    serde_json::to_value(toml_values).unwrap()
+}
+
+pub fn parse_field_element(value: &Value) -> Result<FieldElement, String> {
+    match value {
+        Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(positive_number), _) => Ok(FieldElement::from(positive_number as u128)),
+            (None, Some(negative_number)) => {
+                Ok(-FieldElement::from(negative_number.unsigned_abs() as u128))
+            }
+            (None, None) => Err(format!("expected an integer but found {}", number)),
+        },
+        Value::String(string) => _parse_field_element_from_string(string.trim()),
+        Value::Bool(boolean) => Ok(FieldElement::from(*boolean as u128)),
+        other => Err(format!("expected a field element but found {}", other)),
+    }
+}
+
+// A json array lists the values positionally: public parameters and then the return value
+fn _values_by_name(abi: &Value, values: &Value) -> Value {
+    match values.as_array() {
+        None => values.clone(),
+        Some(positional_values) => {
+            let mut names: Vec<String> = _public_parameters(abi)
+                .iter()
+                .map(|parameter| parameter["name"].as_str().unwrap().to_string())
+                .collect();
+            if !abi["return_type"]["abi_type"].is_null() {
+                names.push("return".to_string());
+            }
+            assert_eq!(
+                names.len(),
+                positional_values.len(),
+                "Expected one value for each public parameter and the return value"
+            );
+            Value::Object(
+                names
+                    .into_iter()
+                    .zip(positional_values.iter().cloned())
+                    .collect(),
+            )
+        }
+    }
+}
+
+fn _public_parameters(abi: &Value) -> Vec<&Value> {
+    _parameters(abi)
+        .into_iter()
+        .filter(|parameter| parameter["visibility"] == "public")
+        .collect()
+}
+
+fn _parameters(abi: &Value) -> Vec<&Value> {
+    abi["parameters"]
+        .as_array()
+        .expect("The program ABI must list its parameters")
+        .iter()
+        .collect()
+}
+
+fn _flatten_value(
+    path: String,
+    abi_type: &Value,
+    value: &Value,
+    flattened_values: &mut Vec<(String, FieldElement)>,
+) {
+    match abi_type["kind"]
+        .as_str()
+        .expect("ABI types must have a kind")
+    {
+        "field" | "boolean" => {
+            let field_element = _parse_field_element_of(&path, value);
+            flattened_values.push((path, field_element));
+        }
+        "integer" => {
+            let integer = _parse_integer(&path, abi_type, value);
+            flattened_values.push((path, integer));
+        }
+        "string" => {
+            let string = value
+                .as_str()
+                .unwrap_or_else(|| panic!("Expected a string for `{}`", path));
+            for (position, byte) in string.bytes().enumerate() {
+                flattened_values.push((
+                    format!("{}[{}]", path, position),
+                    FieldElement::from(byte as u128),
+                ));
+            }
+        }
+        "array" => {
+            let elements = value
+                .as_array()
+                .unwrap_or_else(|| panic!("Expected an array for `{}`", path));
+            for (position, element) in elements.iter().enumerate() {
+                _flatten_value(
+                    format!("{}[{}]", path, position),
+                    &abi_type["type"],
+                    element,
+                    flattened_values,
+                );
+            }
+        }
+        "struct" => {
+            for field in abi_type["fields"].as_array().unwrap() {
+                let field_name = field["name"].as_str().unwrap();
+                let field_value = value
+                    .get(field_name)
+                    .unwrap_or_else(|| panic!("Missing value for `{}.{}`", path, field_name));
+                _flatten_value(
+                    format!("{}.{}", path, field_name),
+                    &field["type"],
+                    field_value,
+                    flattened_values,
+                );
+            }
+        }
+        "tuple" => {
+            for (position, field_type) in abi_type["fields"].as_array().unwrap().iter().enumerate()
+            {
+                let field_value = value
+                    .get(position)
+                    .unwrap_or_else(|| panic!("Missing value for `{}.{}`", path, position));
+                _flatten_value(
+                    format!("{}.{}", path, position),
+                    field_type,
+                    field_value,
+                    flattened_values,
+                );
+            }
+        }
+        kind => panic!("ABI type not supported yet: {}", kind),
+    }
+}
+
+fn _parse_field_element_of(path: &String, value: &Value) -> FieldElement {
+    parse_field_element(value)
+        .unwrap_or_else(|error| panic!("Invalid value for `{}`: {}", path, error))
+}
+
+// Noir lays out negative signed integers in two's complement over the width of their type
+fn _parse_integer(path: &String, integer_type: &Value, value: &Value) -> FieldElement {
+    let field_element = _parse_field_element_of(path, value);
+    let is_negative = match value {
+        Value::Number(number) => number.as_i64().is_some_and(|number| number < 0),
+        Value::String(string) => string.trim().starts_with('-'),
+        _ => false,
+    };
+    let is_signed = integer_type["sign"] == "signed";
+    if is_negative && !is_signed {
+        panic!("Unsigned integer `{}` cannot be negative", path);
+    }
+    let width = integer_type["width"]
+        .as_u64()
+        .expect("ABI integers must have a width") as usize;
+    if !is_negative {
+        // The top bit of a signed integer is its sign
+        let value_bits = if is_signed { width - 1 } else { width };
+        let value = BigUint::from_bytes_be(&field_element.to_be_bytes());
+        if value >= BigUint::from(1u8) << value_bits {
+            panic!(
+                "`{}` does not fit in {} integer of {} bits",
+                path,
+                if is_signed { "a signed" } else { "an unsigned" },
+                width
+            );
+        }
+        return field_element;
+    }
+    let absolute_value = BigUint::from_bytes_be(&(-field_element).to_be_bytes());
+    if absolute_value > BigUint::from(1u8) << (width - 1) {
+        panic!(
+            "`{}` does not fit in a signed integer of {} bits",
+            path, width
+        );
+    }
+    let twos_complement = (BigUint::from(1u8) << width) - absolute_value;
+    FieldElement::from_be_bytes_reduce(&twos_complement.to_bytes_be())
+}
+
+fn _parse_field_element_from_string(string: &str) -> Result<FieldElement, String> {
+    if let Some(absolute_value) = string.strip_prefix('-') {
+        return Ok(-_parse_field_element_from_string(absolute_value)?);
+    }
+    let number = match string.strip_prefix("0x") {
+        Some(hex_digits) => BigUint::parse_bytes(hex_digits.as_bytes(), 16),
+        None => BigUint::parse_bytes(string.as_bytes(), 10),
+    }
+    .ok_or(format!("invalid field element {}", string))?;
+    Ok(FieldElement::from_be_bytes_reduce(&number.to_bytes_be()))
+}
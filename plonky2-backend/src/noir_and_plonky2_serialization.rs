@@ -1,26 +1,349 @@
 use base64;
 use base64::Engine;
+use plonky2::field::types::PrimeField64;
+use plonky2::hash::hash_types::HashOut;
+use plonky2::iop::target::Target;
+use plonky2::plonk::circuit_data::{CircuitData, CommonCircuitData};
+use plonky2::plonk::config::{GenericHashOut, Hasher, PoseidonGoldilocksConfig};
+use plonky2::util::serialization::{Buffer, Read as BufferRead, Write as BufferWrite};
 use serde_json;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 
 use super::*;
+use crate::circuit_translation::generator_serializer::BackendGeneratorSerializer;
+use crate::proof_aggregation::{AggregatedProofLeaf, AggregationConfig};
 
-pub fn deserialize_verifying_key_within_file_path(
+// Every proof, verifying key and proving key file starts with a header that identifies it, so files
+// from another backend, format version or config are rejected before parsing them
+const FILE_MAGIC: &[u8; 8] = b"PLK2ACIR";
+const FILE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FileKind {
+    VerifyingKey = 0,
+    Proof = 1,
+    ProvingKey = 2,
+    AggregatedProof = 3,
+}
+
+impl FileKind {
+    fn name(&self) -> &str {
+        match self {
+            FileKind::VerifyingKey => "verifying key",
+            FileKind::Proof => "proof",
+            FileKind::ProvingKey => "proving key",
+            FileKind::AggregatedProof => "aggregated proof",
+        }
+    }
+}
+
+// Verifying keys start with whether their circuit emulates BN254, followed by the id of their
+// security profile, or NO_SECURITY_PROFILE_ID
+const NO_SECURITY_PROFILE_ID: u8 = u8::MAX;
+
+// The settings of the circuit a verifying key was built with, which verify reads back
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct VerifyingKeySettings {
+    pub security_profile: Option<SecurityProfile>,
+    pub emulate_bn254: bool,
+}
+
+pub fn serialize_verifying_key<C: BackendConfig>(
+    verifier_data: &VerifierCircuitData<F, C, D>,
+) -> Vec<u8> {
+    serialize_verifying_key_of_mode(verifier_data, false)
+}
+
+pub fn serialize_verifying_key_of_mode<C: BackendConfig>(
+    verifier_data: &VerifierCircuitData<F, C, D>,
+    emulate_bn254: bool,
+) -> Vec<u8> {
+    let gate_serializer = DefaultGateSerializer;
+    let security_profile_id = SecurityProfile::of_config(&verifier_data.common.config)
+        .map(|security_profile| security_profile.id())
+        .unwrap_or(NO_SECURITY_PROFILE_ID);
+    let mut bytes = vec![emulate_bn254 as u8, security_profile_id];
+    bytes.extend(verifier_data.to_bytes(&gate_serializer).unwrap());
+    _with_header::<C>(
+        FileKind::VerifyingKey,
+        &verifier_data.verifier_only.circuit_digest.to_bytes(),
+        bytes,
+    )
+}
+
+pub fn serialize_compressed_proof<C: BackendConfig>(
+    compressed_proof: &CompressedProofWithPublicInputs<F, C, D>,
+    circuit_digest: &<C::Hasher as Hasher<F>>::Hash,
+) -> Vec<u8> {
+    _with_header::<C>(
+        FileKind::Proof,
+        &circuit_digest.to_bytes(),
+        compressed_proof.to_bytes(),
+    )
+}
+
+pub fn deserialize_verifying_key_within_file_path<C: BackendConfig>(
     verifying_key_path: &String,
 ) -> VerifierCircuitData<F, C, D> {
     let buffer = read_file_to_bytes(verifying_key_path);
+    deserialize_verifying_key(&buffer)
+}
+
+pub fn deserialize_verifying_key<C: BackendConfig>(bytes: &[u8]) -> VerifierCircuitData<F, C, D> {
+    deserialize_verifying_key_with_security_profile(bytes).0
+}
+
+pub fn deserialize_verifying_key_with_security_profile<C: BackendConfig>(
+    bytes: &[u8],
+) -> (VerifierCircuitData<F, C, D>, Option<SecurityProfile>) {
+    let (verifier_data, settings) = deserialize_verifying_key_with_settings(bytes);
+    (verifier_data, settings.security_profile)
+}
+
+pub fn deserialize_verifying_key_with_settings<C: BackendConfig>(
+    bytes: &[u8],
+) -> (VerifierCircuitData<F, C, D>, VerifyingKeySettings) {
+    let (circuit_digest, payload) = _split_header::<C>(bytes, FileKind::VerifyingKey);
+    let [emulate_bn254, security_profile_id, serialized_verifier_data @ ..] = payload else {
+        panic!("The verifying key is corrupted");
+    };
+    let emulate_bn254 = match emulate_bn254 {
+        0 => false,
+        1 => true,
+        _ => panic!("The verifying key is corrupted"),
+    };
     let gate_serializer = DefaultGateSerializer;
-    VerifierCircuitData::from_bytes(buffer, &gate_serializer).unwrap()
+    let verifier_data =
+        VerifierCircuitData::from_bytes(serialized_verifier_data.to_vec(), &gate_serializer)
+            .expect("The verifying key is corrupted");
+    if verifier_data.verifier_only.circuit_digest.to_bytes() != circuit_digest {
+        panic!("The verifying key is corrupted: its circuit digest does not match its header");
+    }
+    let security_profile = SecurityProfile::from_id(*security_profile_id);
+    if security_profile != SecurityProfile::of_config(&verifier_data.common.config) {
+        panic!("The verifying key is corrupted: its security profile does not match its FRI parameters");
+    }
+    (
+        verifier_data,
+        VerifyingKeySettings {
+            security_profile,
+            emulate_bn254,
+        },
+    )
 }
 
-pub fn deserialize_proof_within_file_path(
+pub fn deserialize_proof_within_file_path<C: BackendConfig>(
     proof_path: &String,
     verifier_data: &VerifierCircuitData<F, C, D>,
 ) -> CompressedProofWithPublicInputs<F, C, D> {
     let buffer = read_file_to_bytes(proof_path);
+    deserialize_proof(&buffer, verifier_data)
+}
+
+pub fn deserialize_proof<C: BackendConfig>(
+    bytes: &[u8],
+    verifier_data: &VerifierCircuitData<F, C, D>,
+) -> CompressedProofWithPublicInputs<F, C, D> {
+    let (circuit_digest, payload) = _split_header::<C>(bytes, FileKind::Proof);
+    _check_circuit_digest(circuit_digest, verifier_data, FileKind::Proof);
     let common_circuit_data = &verifier_data.common;
-    let proof: CompressedProofWithPublicInputs<F, C, D> =
-        CompressedProofWithPublicInputs::from_bytes(buffer, common_circuit_data).unwrap();
-    proof
+    CompressedProofWithPublicInputs::from_bytes(payload.to_vec(), common_circuit_data)
+        .expect("The proof is corrupted")
+}
+
+fn _check_circuit_digest<C: BackendConfig>(
+    circuit_digest: &[u8],
+    verifier_data: &VerifierCircuitData<F, C, D>,
+    kind: FileKind,
+) {
+    if verifier_data.verifier_only.circuit_digest.to_bytes() != circuit_digest {
+        panic!(
+            "The {} was generated for a different circuit than the verifying key",
+            kind.name()
+        );
+    }
+}
+
+// Besides the root proof of the aggregation tree, an aggregated proof lists the circuit digest
+// and public inputs of every proof it aggregates, which its own public inputs commit to
+pub fn serialize_aggregated_proof(
+    leaves: &Vec<AggregatedProofLeaf>,
+    compressed_proof: &CompressedProofWithPublicInputs<F, AggregationConfig, D>,
+    circuit_digest: &HashOut<F>,
+) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::new();
+    bytes.write_usize(leaves.len()).unwrap();
+    for leaf in leaves {
+        bytes
+            .write_field_vec(&leaf.circuit_digest.elements)
+            .unwrap();
+        bytes.write_field_vec(&leaf.public_inputs).unwrap();
+    }
+    let serialized_proof = compressed_proof.to_bytes();
+    bytes.write_usize(serialized_proof.len()).unwrap();
+    BufferWrite::write_all(&mut bytes, &serialized_proof).unwrap();
+    _with_header::<AggregationConfig>(FileKind::AggregatedProof, &circuit_digest.to_bytes(), bytes)
+}
+
+// The leaves are needed to build the aggregation tree, so they can be read before the root proof
+pub fn deserialize_aggregated_proof_leaves(bytes: &[u8]) -> Vec<AggregatedProofLeaf> {
+    let (_, payload) = _split_header::<AggregationConfig>(bytes, FileKind::AggregatedProof);
+    let mut buffer = Buffer::new(payload);
+    _read_aggregated_proof_leaves(&mut buffer)
+}
+
+pub fn deserialize_aggregated_proof(
+    bytes: &[u8],
+    verifier_data: &VerifierCircuitData<F, AggregationConfig, D>,
+) -> CompressedProofWithPublicInputs<F, AggregationConfig, D> {
+    let (circuit_digest, payload) =
+        _split_header::<AggregationConfig>(bytes, FileKind::AggregatedProof);
+    _check_circuit_digest(circuit_digest, verifier_data, FileKind::AggregatedProof);
+    let mut buffer = Buffer::new(payload);
+    _read_aggregated_proof_leaves(&mut buffer);
+    let proof_length = buffer
+        .read_usize()
+        .expect("The aggregated proof is corrupted");
+    let mut serialized_proof = vec![0u8; proof_length];
+    buffer
+        .read_exact(&mut serialized_proof)
+        .expect("The aggregated proof is corrupted");
+    CompressedProofWithPublicInputs::from_bytes(serialized_proof, &verifier_data.common)
+        .expect("The aggregated proof is corrupted")
+}
+
+fn _read_aggregated_proof_leaves(buffer: &mut Buffer) -> Vec<AggregatedProofLeaf> {
+    let amount_of_leaves = buffer
+        .read_usize()
+        .expect("The aggregated proof is corrupted");
+    (0..amount_of_leaves)
+        .map(|_| AggregatedProofLeaf {
+            circuit_digest: HashOut::from_vec(
+                buffer
+                    .read_field_vec()
+                    .expect("The aggregated proof is corrupted"),
+            ),
+            public_inputs: buffer
+                .read_field_vec()
+                .expect("The aggregated proof is corrupted"),
+        })
+        .collect()
+}
+
+// The shape of the proofs a program verifies in-circuit is given by the verifying keys of the
+// inner programs, which must use Poseidon to be verified in-circuit
+pub fn deserialize_recursive_common_data_within_file_paths(
+    verifying_key_paths: &Vec<String>,
+) -> Vec<CommonCircuitData<F, D>> {
+    verifying_key_paths
+        .iter()
+        .map(|verifying_key_path| {
+            deserialize_verifying_key_within_file_path::<PoseidonGoldilocksConfig>(
+                verifying_key_path,
+            )
+            .common
+        })
+        .collect()
+}
+
+// The hash config of a file can be read before knowing which config to parse it with
+pub fn hash_config_within_file_path(file_path: &String, kind: FileKind) -> HashConfig {
+    let bytes = read_file_to_bytes(file_path);
+    let (hash_config_name, _, _) = _read_header(&bytes, kind);
+    HashConfig::from_name(&hash_config_name)
+}
+
+fn _with_header<C: BackendConfig>(
+    kind: FileKind,
+    circuit_digest: &[u8],
+    payload: Vec<u8>,
+) -> Vec<u8> {
+    let hash_config_name = C::HASH_CONFIG.name();
+    let mut bytes: Vec<u8> = Vec::new();
+    bytes.extend_from_slice(FILE_MAGIC);
+    bytes.extend_from_slice(&FILE_FORMAT_VERSION.to_le_bytes());
+    bytes.push(kind as u8);
+    bytes.push(hash_config_name.len() as u8);
+    bytes.extend_from_slice(hash_config_name.as_bytes());
+    bytes.extend_from_slice(&(D as u32).to_le_bytes());
+    bytes.extend_from_slice(&(circuit_digest.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(circuit_digest);
+    bytes.extend(payload);
+    bytes
+}
+
+// Returns the circuit digest in the header and the bytes that follow it
+fn _split_header<C: BackendConfig>(bytes: &[u8], expected_kind: FileKind) -> (&[u8], &[u8]) {
+    let (hash_config_name, circuit_digest, payload) = _read_header(bytes, expected_kind);
+    if hash_config_name != C::HASH_CONFIG.name() {
+        panic!(
+            "The {} file uses the {} hash config, but {} was expected",
+            expected_kind.name(),
+            hash_config_name,
+            C::HASH_CONFIG.name()
+        );
+    }
+    (circuit_digest, payload)
+}
+
+fn _read_header(bytes: &[u8], expected_kind: FileKind) -> (String, &[u8], &[u8]) {
+    let mut reader = HeaderReader { bytes };
+    if reader.take(FILE_MAGIC.len()) != FILE_MAGIC {
+        panic!(
+            "The file is not a {} written by this backend",
+            expected_kind.name()
+        );
+    }
+    let version = reader.take_u32();
+    if version != FILE_FORMAT_VERSION {
+        panic!(
+            "The {} file has format version {}, but this backend reads version {}",
+            expected_kind.name(),
+            version,
+            FILE_FORMAT_VERSION
+        );
+    }
+    let kind = reader.take(1)[0];
+    if kind != expected_kind as u8 {
+        panic!(
+            "Expected a {} file, but found a different kind of file",
+            expected_kind.name()
+        );
+    }
+    let hash_config_length = reader.take(1)[0] as usize;
+    let hash_config_name = String::from_utf8_lossy(reader.take(hash_config_length)).to_string();
+    let extension_degree = reader.take_u32();
+    if extension_degree != D as u32 {
+        panic!(
+            "The {} file uses extension degree {}, but {} was expected",
+            expected_kind.name(),
+            extension_degree,
+            D
+        );
+    }
+    let circuit_digest_length = reader.take_u32() as usize;
+    let circuit_digest = reader.take(circuit_digest_length);
+    (hash_config_name, circuit_digest, reader.bytes)
+}
+
+struct HeaderReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> HeaderReader<'a> {
+    fn take(self: &mut Self, length: usize) -> &'a [u8] {
+        if self.bytes.len() < length {
+            panic!("The file is truncated, its header is incomplete");
+        }
+        let (taken, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        taken
+    }
+
+    fn take_u32(self: &mut Self) -> u32 {
+        u32::from_le_bytes(self.take(4).try_into().unwrap())
+    }
 }
 
 pub fn read_file_to_bytes(file_path: &String) -> Vec<u8> {
@@ -31,13 +354,7 @@ pub fn read_file_to_bytes(file_path: &String) -> Vec<u8> {
 }
 
 pub fn deserialize_program_within_file_path(acir_program_path: &String) -> Program {
-    let mut file = File::open(acir_program_path).expect("There was a problem opening the file");
-    let mut json_string = String::new();
-    file.read_to_string(&mut json_string)
-        .expect("There was a problem reading the file content");
-    let json_str: &str = &json_string;
-    let json: serde_json::Value =
-        serde_json::from_str(json_str).expect("There was a problem parsing the json program");
+    let json = _read_program_json_within_file_path(acir_program_path);
     let Some(bytecode_str) = json["bytecode"].as_str() else {
         panic!("Expected a different circuit format")
     };
@@ -48,6 +365,20 @@ pub fn deserialize_program_within_file_path(acir_program_path: &String) -> Progr
     program.unwrap()
 }
 
+pub fn deserialize_abi_within_file_path(acir_program_path: &String) -> serde_json::Value {
+    let json = _read_program_json_within_file_path(acir_program_path);
+    json["abi"].clone()
+}
+
+fn _read_program_json_within_file_path(acir_program_path: &String) -> serde_json::Value {
+    let mut file = File::open(acir_program_path).expect("There was a problem opening the file");
+    let mut json_string = String::new();
+    file.read_to_string(&mut json_string)
+        .expect("There was a problem reading the file content");
+    let json_str: &str = &json_string;
+    serde_json::from_str(json_str).expect("There was a problem parsing the json program")
+}
+
 pub fn deserialize_witnesses_within_file_path(mut witnesses_path: String) -> WitnessStack {
     witnesses_path.push_str(".gz");
     let file_content: &[u8] = &read_file_to_bytes(&witnesses_path);
@@ -55,7 +386,238 @@ pub fn deserialize_witnesses_within_file_path(mut witnesses_path: String) -> Wit
     witness_stack.unwrap()
 }
 
+// Written with the same .gz extension and format as nargo, so prove can read it back
+pub fn write_witnesses_to_file_path(witness_stack: WitnessStack, mut witnesses_path: String) {
+    witnesses_path.push_str(".gz");
+    let witness_stack_bytes: Vec<u8> =
+        Vec::try_from(witness_stack).expect("There was a problem serializing the witness");
+    write_bytes_to_file_path(witness_stack_bytes, &witnesses_path);
+}
+
 pub fn write_bytes_to_file_path(bytes: Vec<u8>, path: &String) {
     let mut file = File::create(path).expect("Failed to create file");
     file.write_all(&bytes).expect("Failed to write file");
 }
+
+// Field elements are written as a json array of hex strings, which nargo accepts as inputs
+pub fn fields_as_json(fields: &Vec<F>) -> String {
+    let hex_fields: Vec<String> = fields
+        .iter()
+        .map(|field| format!("0x{:016x}", field.to_canonical_u64()))
+        .collect();
+    serde_json::to_string(&hex_fields).unwrap()
+}
+
+pub fn write_fields_as_json_to_file_path(fields: &Vec<F>, path: &String) {
+    write_bytes_to_file_path(fields_as_json(fields).into_bytes(), path);
+}
+
+// The settings of the circuit a proving key was built with, which prove must be run with too
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProvingKeySettings {
+    pub zero_knowledge: bool,
+    pub security_profile: SecurityProfile,
+    pub emulate_bn254: bool,
+    // Hash of the verifying keys given with --recursive-vk, in order
+    pub recursive_vks_hash: Vec<u8>,
+}
+
+impl ProvingKeySettings {
+    pub fn new(
+        zero_knowledge: bool,
+        security_profile: SecurityProfile,
+        emulate_bn254: bool,
+        recursive_vk_paths: &Vec<String>,
+    ) -> Self {
+        let mut hasher = Sha256::new();
+        for recursive_vk_path in recursive_vk_paths {
+            let recursive_vk = read_file_to_bytes(recursive_vk_path);
+            hasher.update((recursive_vk.len() as u64).to_le_bytes());
+            hasher.update(recursive_vk);
+        }
+        Self {
+            zero_knowledge,
+            security_profile,
+            emulate_bn254,
+            recursive_vks_hash: hasher.finalize().to_vec(),
+        }
+    }
+}
+
+// The proving key holds everything prove needs to skip circuit building: the circuit data
+// the witness to target maps of main and of every inlined call, and the computed targets.
+// Its header also holds the settings and the hash of the program it was written for, while the
+// hash config is in the file header
+pub fn write_proving_key_to_file_path<C: BackendConfig>(
+    program: &Program,
+    settings: &ProvingKeySettings,
+    circuit_data: &CircuitData<F, C, D>,
+    witness_target_map: &HashMap<Witness, Target>,
+    inlined_calls: &Vec<InlinedCall>,
+    computed_targets: &HashSet<Target>,
+    proving_key_path: &String,
+) {
+    let serialized_circuit_data = circuit_data
+        .to_bytes(&DefaultGateSerializer, &BackendGeneratorSerializer)
+        .expect("There was a problem serializing the circuit data");
+    let mut bytes: Vec<u8> = Vec::new();
+    bytes.write_bool(settings.zero_knowledge).unwrap();
+    bytes.write_u8(settings.security_profile.id()).unwrap();
+    bytes.write_bool(settings.emulate_bn254).unwrap();
+    bytes
+        .write_usize(settings.recursive_vks_hash.len())
+        .unwrap();
+    BufferWrite::write_all(&mut bytes, &settings.recursive_vks_hash).unwrap();
+    let program_hash = program_hash(program);
+    bytes.write_usize(program_hash.len()).unwrap();
+    BufferWrite::write_all(&mut bytes, &program_hash).unwrap();
+    bytes.write_usize(serialized_circuit_data.len()).unwrap();
+    // Vec<u8> is a std::io::Write too, so the plonky2 trait is named explicitly
+    BufferWrite::write_all(&mut bytes, &serialized_circuit_data).unwrap();
+    _write_witness_target_map(&mut bytes, witness_target_map);
+    bytes.write_usize(inlined_calls.len()).unwrap();
+    for inlined_call in inlined_calls {
+        bytes.write_u32(inlined_call.function_id).unwrap();
+        _write_witness_target_map(&mut bytes, &inlined_call.witness_target_map);
+        _write_call_predicate(&mut bytes, &inlined_call.predicate);
+        bytes
+            .write_usize(inlined_call.amount_of_nested_calls)
+            .unwrap();
+    }
+    let computed_targets: Vec<Target> = computed_targets.iter().cloned().collect();
+    bytes.write_target_vec(&computed_targets).unwrap();
+    let circuit_digest = circuit_data.verifier_only.circuit_digest.to_bytes();
+    let proving_key = _with_header::<C>(FileKind::ProvingKey, &circuit_digest, bytes);
+    write_bytes_to_file_path(proving_key, proving_key_path);
+}
+
+pub fn deserialize_proving_key_within_file_path<C: BackendConfig>(
+    program: &Program,
+    settings: &ProvingKeySettings,
+    proving_key_path: &String,
+) -> (
+    CircuitData<F, C, D>,
+    HashMap<Witness, Target>,
+    Vec<InlinedCall>,
+    HashSet<Target>,
+) {
+    let bytes = read_file_to_bytes(proving_key_path);
+    let (_, payload) = _split_header::<C>(&bytes, FileKind::ProvingKey);
+    let mut buffer = Buffer::new(payload);
+    _check_proving_key_settings(&mut buffer, settings);
+    let program_hash_length = buffer.read_usize().expect("Invalid proving key");
+    let mut proving_key_program_hash = vec![0u8; program_hash_length];
+    buffer
+        .read_exact(&mut proving_key_program_hash)
+        .expect("Invalid proving key");
+    if proving_key_program_hash != program_hash(program) {
+        panic!("The proving key was written for a different program, write it again with write_pk");
+    }
+    let circuit_data_length = buffer.read_usize().expect("Invalid proving key");
+    let mut serialized_circuit_data = vec![0u8; circuit_data_length];
+    buffer
+        .read_exact(&mut serialized_circuit_data)
+        .expect("Invalid proving key");
+    let circuit_data = CircuitData::from_bytes(
+        &serialized_circuit_data,
+        &DefaultGateSerializer,
+        &BackendGeneratorSerializer,
+    )
+    .expect("There was a problem deserializing the circuit data");
+    let witness_target_map = _read_witness_target_map(&mut buffer);
+    let inlined_calls_length = buffer.read_usize().expect("Invalid proving key");
+    let inlined_calls = (0..inlined_calls_length)
+        .map(|_| InlinedCall {
+            function_id: buffer.read_u32().expect("Invalid proving key"),
+            witness_target_map: _read_witness_target_map(&mut buffer),
+            predicate: _read_call_predicate(&mut buffer),
+            amount_of_nested_calls: buffer.read_usize().expect("Invalid proving key"),
+        })
+        .collect();
+    let computed_targets = buffer
+        .read_target_vec()
+        .expect("Invalid proving key")
+        .into_iter()
+        .collect();
+    (
+        circuit_data,
+        witness_target_map,
+        inlined_calls,
+        computed_targets,
+    )
+}
+
+fn _check_proving_key_settings(buffer: &mut Buffer, settings: &ProvingKeySettings) {
+    let zero_knowledge = buffer.read_bool().expect("Invalid proving key");
+    _check_proving_key_flag("--no-zk", !zero_knowledge, !settings.zero_knowledge);
+    let security_profile = SecurityProfile::from_id(buffer.read_u8().expect("Invalid proving key"))
+        .expect("Invalid proving key");
+    if security_profile != settings.security_profile {
+        panic!(
+            "The proving key was written with --profile {}, but prove was run with --profile {}",
+            security_profile.name(),
+            settings.security_profile.name()
+        );
+    }
+    let emulate_bn254 = buffer.read_bool().expect("Invalid proving key");
+    _check_proving_key_flag("--emulate-bn254", emulate_bn254, settings.emulate_bn254);
+    let recursive_vks_hash_length = buffer.read_usize().expect("Invalid proving key");
+    let mut recursive_vks_hash = vec![0u8; recursive_vks_hash_length];
+    buffer
+        .read_exact(&mut recursive_vks_hash)
+        .expect("Invalid proving key");
+    if recursive_vks_hash != settings.recursive_vks_hash {
+        panic!("The proving key was written with other --recursive-vk verifying keys than the ones given to prove");
+    }
+}
+
+fn _check_proving_key_flag(flag: &str, written_with_flag: bool, run_with_flag: bool) {
+    let with_or_without = |has_flag: bool| if has_flag { "with" } else { "without" };
+    if written_with_flag != run_with_flag {
+        panic!(
+            "The proving key was written {} {}, but prove was run {} it",
+            with_or_without(written_with_flag),
+            flag,
+            with_or_without(run_with_flag)
+        );
+    }
+}
+
+pub fn program_hash(program: &Program) -> Vec<u8> {
+    Sha256::digest(Program::serialize_program(program)).to_vec()
+}
+
+fn _write_witness_target_map(bytes: &mut Vec<u8>, witness_target_map: &HashMap<Witness, Target>) {
+    bytes.write_usize(witness_target_map.len()).unwrap();
+    for (witness, target) in witness_target_map {
+        bytes.write_u32(witness.0).unwrap();
+        bytes.write_target(*target).unwrap();
+    }
+}
+
+fn _read_witness_target_map(buffer: &mut Buffer) -> HashMap<Witness, Target> {
+    let length = buffer.read_usize().expect("Invalid proving key");
+    (0..length)
+        .map(|_| {
+            let witness = Witness(buffer.read_u32().expect("Invalid proving key"));
+            let target = buffer.read_target().expect("Invalid proving key");
+            (witness, target)
+        })
+        .collect()
+}
+
+// Call predicates are ACIR expressions, written as json
+fn _write_call_predicate(bytes: &mut Vec<u8>, predicate: &Option<Expression>) {
+    let serialized_predicate = serde_json::to_vec(predicate).unwrap();
+    bytes.write_usize(serialized_predicate.len()).unwrap();
+    BufferWrite::write_all(bytes, &serialized_predicate).unwrap();
+}
+
+fn _read_call_predicate(buffer: &mut Buffer) -> Option<Expression> {
+    let length = buffer.read_usize().expect("Invalid proving key");
+    let mut serialized_predicate = vec![0u8; length];
+    buffer
+        .read_exact(&mut serialized_predicate)
+        .expect("Invalid proving key");
+    serde_json::from_slice(&serialized_predicate).expect("Invalid proving key")
+}
@@ -0,0 +1,161 @@
+use plonky2::fri::reduction_strategies::FriReductionStrategy;
+use plonky2::fri::FriConfig;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2::plonk::config::{KeccakGoldilocksConfig, PoseidonGoldilocksConfig};
+
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HashConfig {
+    Keccak,
+    // Cheaper to verify inside another plonky2 circuit
+    Poseidon,
+}
+
+impl HashConfig {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashConfig::Keccak => "keccak",
+            HashConfig::Poseidon => "poseidon",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "keccak" => HashConfig::Keccak,
+            "poseidon" => HashConfig::Poseidon,
+            other => panic!(
+                "Unknown hash config: {}, expected poseidon or keccak",
+                other
+            ),
+        }
+    }
+}
+
+// The plonky2 configs the backend can prove with, all of them over the Goldilocks field
+pub trait BackendConfig: GenericConfig<D, F = F> + 'static {
+    const HASH_CONFIG: HashConfig;
+}
+
+impl BackendConfig for KeccakGoldilocksConfig {
+    const HASH_CONFIG: HashConfig = HashConfig::Keccak;
+}
+
+impl BackendConfig for PoseidonGoldilocksConfig {
+    const HASH_CONFIG: HashConfig = HashConfig::Poseidon;
+}
+
+// FRI parameters trading proving time, proof size and security. The conjectured security
+// of each profile is rate_bits * num_query_rounds + proof_of_work_bits
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SecurityProfile {
+    // 30 bits, only meant for development and tests
+    FastDev,
+    // 100 bits, plonky2's standard recursion config
+    Standard100Bit,
+    // 100 bits with a higher rate, so proofs are smaller but slower to generate
+    SmallProof,
+}
+
+// Verify refuses verifying keys with less conjectured security unless told otherwise
+pub const DEFAULT_MIN_SECURITY_BITS: usize = 100;
+
+impl SecurityProfile {
+    const ALL: [SecurityProfile; 3] = [
+        SecurityProfile::FastDev,
+        SecurityProfile::Standard100Bit,
+        SecurityProfile::SmallProof,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SecurityProfile::FastDev => "fast-dev",
+            SecurityProfile::Standard100Bit => "standard-100bit",
+            SecurityProfile::SmallProof => "small-proof",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "fast-dev" => SecurityProfile::FastDev,
+            "standard-100bit" => SecurityProfile::Standard100Bit,
+            "small-proof" => SecurityProfile::SmallProof,
+            other => panic!(
+                "Unknown security profile: {}, expected fast-dev, standard-100bit or small-proof",
+                other
+            ),
+        }
+    }
+
+    // Written in the verifying key header
+    pub fn id(&self) -> u8 {
+        match self {
+            SecurityProfile::FastDev => 0,
+            SecurityProfile::Standard100Bit => 1,
+            SecurityProfile::SmallProof => 2,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|profile| profile.id() == id)
+    }
+
+    // Circuits built with other FRI parameters, like the aggregation ones, have no profile
+    pub fn of_config(config: &CircuitConfig) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|profile| profile.fri_config() == config.fri_config)
+    }
+
+    pub fn fri_config(&self) -> FriConfig {
+        let (rate_bits, num_query_rounds, proof_of_work_bits) = match self {
+            SecurityProfile::FastDev => (3, 10, 0),
+            SecurityProfile::Standard100Bit => (3, 28, 16),
+            SecurityProfile::SmallProof => (6, 14, 16),
+        };
+        FriConfig {
+            rate_bits,
+            cap_height: 4,
+            proof_of_work_bits,
+            reduction_strategy: FriReductionStrategy::ConstantArityBits(4, 5),
+            num_query_rounds,
+        }
+    }
+}
+
+pub fn conjectured_security_bits(config: &CircuitConfig) -> usize {
+    let fri_config = &config.fri_config;
+    fri_config.rate_bits * fri_config.num_query_rounds + fri_config.proof_of_work_bits as usize
+}
+
+// The FRI parameters are part of every verifying key, so verify, aggregate and verify_aggregate
+// check each key they are given against the same minimum
+pub fn check_minimum_security(
+    verifying_key_name: &str,
+    security_profile: Option<SecurityProfile>,
+    config: &CircuitConfig,
+    min_security_bits: Option<usize>,
+) {
+    let min_security_bits = min_security_bits.unwrap_or(DEFAULT_MIN_SECURITY_BITS);
+    let security_bits = conjectured_security_bits(config);
+    if security_bits < min_security_bits {
+        let security_profile_name = security_profile
+            .map(|security_profile| security_profile.name())
+            .unwrap_or("custom");
+        panic!(
+            "{} was built with the {} profile, which provides {} bits of conjectured security, but at least {} are required (see --min-security)",
+            verifying_key_name, security_profile_name, security_bits, min_security_bits
+        );
+    }
+}
+
+// Zero knowledge blinds the witness polynomials, so proofs reveal nothing about private inputs
+pub fn circuit_config(zero_knowledge: bool, security_profile: SecurityProfile) -> CircuitConfig {
+    let mut config = match zero_knowledge {
+        true => CircuitConfig::standard_recursion_zk_config(),
+        false => CircuitConfig::standard_recursion_config(),
+    };
+    config.fri_config = security_profile.fri_config();
+    config.security_bits = conjectured_security_bits(&config);
+    config
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use plonky2::hash::hash_types::{HashOut, HashOutTarget};
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::{CircuitData, CommonCircuitData, VerifierOnlyCircuitData};
+use plonky2::plonk::config::{GenericHashOut, Hasher, PoseidonGoldilocksConfig};
+use plonky2::plonk::proof::{ProofWithPublicInputs, ProofWithPublicInputsTarget};
+
+use super::*;
+
+// Verifying a proof inside another plonky2 circuit needs an algebraic hash, so both the
+// aggregated proofs and the aggregation tree use Poseidon
+pub type AggregationConfig = PoseidonGoldilocksConfig;
+
+// What the root of the aggregation tree commits to for each aggregated proof: the program it
+// proves, identified by its circuit digest, and its public inputs
+#[derive(Clone, Debug, PartialEq)]
+pub struct AggregatedProofLeaf {
+    pub circuit_digest: HashOut<F>,
+    pub public_inputs: Vec<F>,
+}
+
+// Each node of the tree verifies two proofs of the level below and exposes a commitment,
+// H(left || right), where a leaf is hashed as H(circuit digest || public inputs) and any
+// other node contributes its own commitment. A level with an odd amount of nodes pairs the
+// last one with itself
+struct AggregationCircuit {
+    circuit_data: CircuitData<F, AggregationConfig, D>,
+    left_proof: ProofWithPublicInputsTarget<D>,
+    right_proof: ProofWithPublicInputsTarget<D>,
+}
+
+impl AggregationCircuit {
+    fn prove(
+        self: &Self,
+        left_proof: &ProofWithPublicInputs<F, AggregationConfig, D>,
+        right_proof: &ProofWithPublicInputs<F, AggregationConfig, D>,
+    ) -> ProofWithPublicInputs<F, AggregationConfig, D> {
+        let mut witnesses = PartialWitness::<F>::new();
+        witnesses.set_proof_with_pis_target(&self.left_proof, left_proof);
+        witnesses.set_proof_with_pis_target(&self.right_proof, right_proof);
+        self.circuit_data
+            .prove(witnesses)
+            .expect("There was a problem aggregating the proofs")
+    }
+}
+
+// Nodes that verify the same pair of circuits share their aggregation circuit, so aggregating
+// proofs of a single program only builds one circuit per level
+pub struct AggregationTree {
+    circuits: Vec<AggregationCircuit>,
+    levels: Vec<Vec<usize>>,
+}
+
+impl AggregationTree {
+    pub fn root_verifier_data(self: &Self) -> VerifierCircuitData<F, AggregationConfig, D> {
+        let root_level = self.levels.last().unwrap();
+        self.circuits[root_level[0]].circuit_data.verifier_data()
+    }
+
+    pub fn prove(
+        self: &Self,
+        proofs: Vec<ProofWithPublicInputs<F, AggregationConfig, D>>,
+    ) -> ProofWithPublicInputs<F, AggregationConfig, D> {
+        let mut proofs = proofs;
+        for level in &self.levels {
+            assert_eq!(
+                level.len(),
+                (proofs.len() + 1) / 2,
+                "The aggregation tree does not match the amount of proofs"
+            );
+            proofs = _pairs_of(proofs)
+                .iter()
+                .zip(level)
+                .map(|((left_proof, right_proof), circuit_index)| {
+                    self.circuits[*circuit_index].prove(left_proof, right_proof)
+                })
+                .collect();
+        }
+        proofs.pop().unwrap()
+    }
+}
+
+// The circuits only depend on the verifying key of every leaf, so the verifier can build them
+// again to check an aggregated proof
+pub fn build_aggregation_tree(
+    leaf_verifier_data: &Vec<&VerifierCircuitData<F, AggregationConfig, D>>,
+) -> AggregationTree {
+    if leaf_verifier_data.is_empty() {
+        panic!("Must specify at least one proof to aggregate");
+    }
+    let mut tree = AggregationTree {
+        circuits: Vec::new(),
+        levels: Vec::new(),
+    };
+    let mut circuit_indices: HashMap<(bool, Vec<u8>, Vec<u8>), usize> = HashMap::new();
+    let mut nodes: Vec<(
+        VerifierOnlyCircuitData<AggregationConfig, D>,
+        CommonCircuitData<F, D>,
+    )> = leaf_verifier_data
+        .iter()
+        .map(|verifier_data| {
+            (
+                verifier_data.verifier_only.clone(),
+                verifier_data.common.clone(),
+            )
+        })
+        .collect();
+    let mut children_are_leaves = true;
+    loop {
+        let mut level = Vec::new();
+        let mut next_nodes = Vec::new();
+        for (left, right) in _pairs_of(nodes) {
+            let key = (
+                children_are_leaves,
+                left.0.circuit_digest.to_bytes(),
+                right.0.circuit_digest.to_bytes(),
+            );
+            let circuit_index = *circuit_indices.entry(key).or_insert_with(|| {
+                tree.circuits.push(_build_aggregation_circuit(
+                    &left,
+                    &right,
+                    children_are_leaves,
+                ));
+                tree.circuits.len() - 1
+            });
+            let circuit_data = &tree.circuits[circuit_index].circuit_data;
+            level.push(circuit_index);
+            next_nodes.push((
+                circuit_data.verifier_only.clone(),
+                circuit_data.common.clone(),
+            ));
+        }
+        tree.levels.push(level);
+        nodes = next_nodes;
+        children_are_leaves = false;
+        if nodes.len() == 1 {
+            return tree;
+        }
+    }
+}
+
+fn _build_aggregation_circuit(
+    left: &(
+        VerifierOnlyCircuitData<AggregationConfig, D>,
+        CommonCircuitData<F, D>,
+    ),
+    right: &(
+        VerifierOnlyCircuitData<AggregationConfig, D>,
+        CommonCircuitData<F, D>,
+    ),
+    children_are_leaves: bool,
+) -> AggregationCircuit {
+    let mut builder =
+        CircuitBuilder::<F, D>::new(circuit_config(true, SecurityProfile::Standard100Bit));
+    let left_verifier_data = builder.constant_verifier_data(&left.0);
+    let right_verifier_data = builder.constant_verifier_data(&right.0);
+    let left_proof = builder.add_virtual_proof_with_pis(&left.1);
+    let right_proof = builder.add_virtual_proof_with_pis(&right.1);
+    builder.verify_proof::<AggregationConfig>(&left_proof, &left_verifier_data, &left.1);
+    builder.verify_proof::<AggregationConfig>(&right_proof, &right_verifier_data, &right.1);
+
+    let (left_hash, right_hash) = match children_are_leaves {
+        true => (
+            builder.hash_n_to_hash_no_pad::<PoseidonHash>(
+                [
+                    left_verifier_data.circuit_digest.elements.to_vec(),
+                    left_proof.public_inputs.clone(),
+                ]
+                .concat(),
+            ),
+            builder.hash_n_to_hash_no_pad::<PoseidonHash>(
+                [
+                    right_verifier_data.circuit_digest.elements.to_vec(),
+                    right_proof.public_inputs.clone(),
+                ]
+                .concat(),
+            ),
+        ),
+        false => (
+            HashOutTarget::from_vec(left_proof.public_inputs.clone()),
+            HashOutTarget::from_vec(right_proof.public_inputs.clone()),
+        ),
+    };
+    let commitment = builder
+        .hash_n_to_hash_no_pad::<PoseidonHash>([left_hash.elements, right_hash.elements].concat());
+    builder.register_public_inputs(&commitment.elements);
+
+    AggregationCircuit {
+        circuit_data: builder.build::<AggregationConfig>(),
+        left_proof,
+        right_proof,
+    }
+}
+
+// The same commitment the root of the aggregation tree exposes as its public inputs
+pub fn leaves_commitment(leaves: &Vec<AggregatedProofLeaf>) -> Vec<F> {
+    if leaves.is_empty() {
+        panic!("Must specify at least one proof to aggregate");
+    }
+    let mut nodes: Vec<HashOut<F>> = leaves
+        .iter()
+        .map(|leaf| {
+            PoseidonHash::hash_no_pad(
+                &[
+                    leaf.circuit_digest.elements.to_vec(),
+                    leaf.public_inputs.clone(),
+                ]
+                .concat(),
+            )
+        })
+        .collect();
+    loop {
+        nodes = _pairs_of(nodes)
+            .iter()
+            .map(|(left, right)| {
+                PoseidonHash::hash_no_pad(&[left.elements, right.elements].concat())
+            })
+            .collect();
+        if nodes.len() == 1 {
+            return nodes[0].elements.to_vec();
+        }
+    }
+}
+
+fn _pairs_of<T: Clone>(mut nodes: Vec<T>) -> Vec<(T, T)> {
+    if nodes.len() % 2 == 1 {
+        nodes.push(nodes.last().unwrap().clone());
+    }
+    nodes
+        .chunks(2)
+        .map(|pair| (pair[0].clone(), pair[1].clone()))
+        .collect()
+}
@@ -0,0 +1,369 @@
+use acir::circuit::opcodes::{BlackBoxFuncCall, FunctionInput};
+use acir::circuit::{AssertionPayload, OpcodeLocation};
+use plonky2::field::types::{Field, PrimeField64};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+use super::*;
+use crate::circuit_translation::constant_analysis::field_element_to_goldilocks_field;
+use crate::circuit_translation::recursive_aggregation_translator;
+
+#[cfg(test)]
+mod tests;
+
+// Evaluates every opcode against the witness the same way the translated circuit does,
+// so a wrong witness is reported with its ACIR opcode instead of failing inside plonky2
+pub fn check_witness_stack(program: &Program, witness_stack: &WitnessStack) {
+    let mut witness_stack = witness_stack.clone();
+    let main_stack_item = witness_stack.pop().expect("The witness stack is empty");
+    _check_function_witness(
+        program,
+        &mut witness_stack,
+        main_stack_item.index,
+        &main_stack_item.witness,
+    );
+}
+
+// Calls push the witness of the called function when they return, so the witness of the last
+// call of a function is on top of the stack, followed by the witnesses of its nested calls
+fn _check_function_witness(
+    program: &Program,
+    witness_stack: &mut WitnessStack,
+    function_id: u32,
+    witness_map: &WitnessMap,
+) {
+    let circuit = &program.functions[function_id as usize];
+    let mut callee_witness_maps: HashMap<usize, WitnessMap> = HashMap::new();
+    for (opcode_index, opcode) in circuit.opcodes.iter().enumerate().rev() {
+        let Opcode::Call { id, predicate, .. } = opcode else {
+            continue;
+        };
+        let checker = WitnessChecker::new(program, witness_map, &callee_witness_maps);
+        match checker._call_is_enabled(predicate) {
+            Ok(false) => continue,
+            Ok(true) => {}
+            Err(reason) => panic!(
+                "Function {}: {}",
+                function_id,
+                _report_for(circuit, opcode_index, opcode, reason)
+            ),
+        }
+        let stack_item = witness_stack
+            .pop()
+            .expect("The witness stack does not match the function calls of the program");
+        assert_eq!(
+            *id, stack_item.index,
+            "The witness stack does not match the function calls of the program"
+        );
+        _check_function_witness(
+            program,
+            witness_stack,
+            stack_item.index,
+            &stack_item.witness,
+        );
+        callee_witness_maps.insert(opcode_index, stack_item.witness);
+    }
+    if let Err(report) = check_circuit_witness(program, circuit, witness_map, &callee_witness_maps)
+    {
+        panic!("Function {}: {}", function_id, report);
+    }
+}
+
+// The witness maps of the functions called by the circuit are given by the index of the enabled
+// Call opcode that called them
+pub fn check_circuit_witness(
+    program: &Program,
+    circuit: &Circuit,
+    witness_map: &WitnessMap,
+    callee_witness_maps: &HashMap<usize, WitnessMap>,
+) -> Result<(), String> {
+    let mut checker = WitnessChecker::new(program, witness_map, callee_witness_maps);
+    for (opcode_index, opcode) in circuit.opcodes.iter().enumerate() {
+        if let Err(reason) = checker.check_opcode(opcode_index, opcode) {
+            return Err(_report_for(circuit, opcode_index, opcode, reason));
+        }
+    }
+    Ok(())
+}
+
+struct WitnessChecker<'a> {
+    program: &'a Program,
+    witness_map: &'a WitnessMap,
+    callee_witness_maps: &'a HashMap<usize, WitnessMap>,
+    memory_blocks: HashMap<u32, Vec<F>>,
+}
+
+impl<'a> WitnessChecker<'a> {
+    fn new(
+        program: &'a Program,
+        witness_map: &'a WitnessMap,
+        callee_witness_maps: &'a HashMap<usize, WitnessMap>,
+    ) -> WitnessChecker<'a> {
+        Self {
+            program,
+            witness_map,
+            callee_witness_maps,
+            memory_blocks: HashMap::new(),
+        }
+    }
+
+    fn check_opcode(self: &mut Self, opcode_index: usize, opcode: &Opcode) -> Result<(), String> {
+        match opcode {
+            Opcode::AssertZero(expr) => match self._evaluate(expr)?.is_zero() {
+                true => Ok(()),
+                false => Err("the expression is not zero".to_string()),
+            },
+            Opcode::MemoryInit {
+                block_id,
+                init,
+                block_type: _,
+            } => {
+                let values = init
+                    .iter()
+                    .map(|witness| self._value_of(witness))
+                    .collect::<Result<Vec<F>, String>>()?;
+                self.memory_blocks.insert(block_id.0, values);
+                Ok(())
+            }
+            Opcode::MemoryOp {
+                block_id,
+                op,
+                predicate,
+            } => {
+                let is_write = !self._evaluate(&op.operation)?.is_zero();
+                if let Some(predicate) = predicate {
+                    // A disabled write is skipped, but a disabled read reads zero
+                    if self._evaluate(predicate)?.is_zero() {
+                        return match is_write || self._evaluate(&op.value)?.is_zero() {
+                            true => Ok(()),
+                            false => {
+                                Err("a read with a false predicate must read zero".to_string())
+                            }
+                        };
+                    }
+                }
+                let index = self._evaluate(&op.index)?.to_canonical_u64() as usize;
+                let value = self._evaluate(&op.value)?;
+                let memory_block = self
+                    .memory_blocks
+                    .get_mut(&block_id.0)
+                    .ok_or("the memory block was not initialized")?;
+                let stored_value = memory_block
+                    .get_mut(index)
+                    .ok_or(format!("index {} is out of bounds", index))?;
+                match (is_write, *stored_value == value) {
+                    (true, _) => {
+                        *stored_value = value;
+                        Ok(())
+                    }
+                    (false, true) => Ok(()),
+                    (false, false) => Err(format!("the value read at index {} is wrong", index)),
+                }
+            }
+            Opcode::BlackBoxFuncCall(func_call) => self._check_blackbox_func_call(func_call),
+            Opcode::Call {
+                id,
+                inputs,
+                outputs,
+                predicate,
+            } => self._check_call(opcode_index, *id, inputs, outputs, predicate),
+            _ => Ok(()),
+        }
+    }
+
+    fn _check_call(
+        self: &Self,
+        opcode_index: usize,
+        function_id: u32,
+        inputs: &Vec<Witness>,
+        outputs: &Vec<Witness>,
+        predicate: &Option<Expression>,
+    ) -> Result<(), String> {
+        if !self._call_is_enabled(predicate)? {
+            // The ACVM skips disabled calls and sets their outputs to zero
+            for output in outputs {
+                if !self._value_of(output)?.is_zero() {
+                    return Err("a disabled call must output zero".to_string());
+                }
+            }
+            return Ok(());
+        }
+        let callee_witness_map = self
+            .callee_witness_maps
+            .get(&opcode_index)
+            .ok_or("the witness stack has no witness for the called function")?;
+        // The callee sees the call inputs as its witnesses 0..n
+        for (position, input) in inputs.iter().enumerate() {
+            let callee_input = _goldilocks_value_in(callee_witness_map, &Witness(position as u32))?;
+            if self._value_of(input)? != callee_input {
+                return Err(format!(
+                    "input {} differs from the one received by the called function",
+                    position
+                ));
+            }
+        }
+        let return_values = &self.program.functions[function_id as usize].return_values.0;
+        if outputs.len() != return_values.len() {
+            return Err(
+                "the outputs do not match the return values of the called function".to_string(),
+            );
+        }
+        for (position, (output, return_value)) in outputs.iter().zip(return_values).enumerate() {
+            if self._value_of(output)? != _goldilocks_value_in(callee_witness_map, return_value)? {
+                return Err(format!(
+                    "output {} differs from the value returned by the called function",
+                    position
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn _call_is_enabled(self: &Self, predicate: &Option<Expression>) -> Result<bool, String> {
+        match predicate {
+            Some(predicate) => Ok(!self._evaluate(predicate)?.is_zero()),
+            None => Ok(true),
+        }
+    }
+
+    fn _check_blackbox_func_call(
+        self: &mut Self,
+        func_call: &BlackBoxFuncCall<FieldElement>,
+    ) -> Result<(), String> {
+        match func_call {
+            BlackBoxFuncCall::RANGE { input } => {
+                let value = self._field_element_of(&input.witness)?;
+                match value.num_bits() <= input.num_bits {
+                    true => Ok(()),
+                    false => Err(format!("the value does not fit in {} bits", input.num_bits)),
+                }
+            }
+            BlackBoxFuncCall::AND { lhs, rhs, output } => {
+                self._check_bitwise_operation(lhs, rhs, output, |x, y| x & y)
+            }
+            BlackBoxFuncCall::XOR { lhs, rhs, output } => {
+                self._check_bitwise_operation(lhs, rhs, output, |x, y| x ^ y)
+            }
+            BlackBoxFuncCall::SHA256 { inputs, outputs } => {
+                let message = inputs
+                    .iter()
+                    .map(|input| {
+                        // The message is made of bytes, whatever width the input declares
+                        let num_bits = input.num_bits.min(8);
+                        let value = self._field_element_of(&input.witness)?;
+                        match value.num_bits() <= num_bits {
+                            true => Ok(value.to_u128() as u8),
+                            false => Err(format!(
+                                "an input of the hash does not fit in {} bits",
+                                num_bits
+                            )),
+                        }
+                    })
+                    .collect::<Result<Vec<u8>, String>>()?;
+                let digest = Sha256::digest(&message);
+                for (output, byte) in outputs.iter().zip(digest.iter()) {
+                    if self._value_of(output)? != F::from_canonical_u8(*byte) {
+                        return Err("the digest is wrong".to_string());
+                    }
+                }
+                Ok(())
+            }
+            BlackBoxFuncCall::RecursiveAggregation {
+                verification_key,
+                proof,
+                public_inputs,
+                key_hash,
+            } => {
+                // The inner proof is only verified by the circuit, which has the common data of
+                // the inner program, but the key hash only depends on the verifying key
+                let verification_key_values = verification_key
+                    .iter()
+                    .map(|input| self._value_of(&input.witness))
+                    .collect::<Result<Vec<F>, String>>()?;
+                for input in proof.iter().chain(public_inputs.iter()) {
+                    self._value_of(&input.witness)?;
+                }
+                match self._value_of(&key_hash.witness)?
+                    == recursive_aggregation_translator::key_hash(&verification_key_values)
+                {
+                    true => Ok(()),
+                    false => Err("the key hash does not match the verifying key".to_string()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn _check_bitwise_operation(
+        self: &Self,
+        lhs: &FunctionInput,
+        rhs: &FunctionInput,
+        output: &Witness,
+        operation: fn(u128, u128) -> u128,
+    ) -> Result<(), String> {
+        let lhs_value = self._field_element_of(&lhs.witness)?.to_u128();
+        let rhs_value = self._field_element_of(&rhs.witness)?.to_u128();
+        let expected_output = FieldElement::from(operation(lhs_value, rhs_value));
+        match self._field_element_of(output)? == expected_output {
+            true => Ok(()),
+            false => Err("the output of the bitwise operation is wrong".to_string()),
+        }
+    }
+
+    fn _evaluate(self: &Self, expr: &Expression) -> Result<F, String> {
+        let mut result = field_element_to_goldilocks_field(&expr.q_c);
+        for (coefficient, lhs, rhs) in &expr.mul_terms {
+            result += field_element_to_goldilocks_field(coefficient)
+                * self._value_of(lhs)?
+                * self._value_of(rhs)?;
+        }
+        for (coefficient, witness) in &expr.linear_combinations {
+            result += field_element_to_goldilocks_field(coefficient) * self._value_of(witness)?;
+        }
+        Ok(result)
+    }
+
+    fn _value_of(self: &Self, witness: &Witness) -> Result<F, String> {
+        _goldilocks_value_in(self.witness_map, witness)
+    }
+
+    fn _field_element_of(self: &Self, witness: &Witness) -> Result<FieldElement, String> {
+        _field_element_in(self.witness_map, witness)
+    }
+}
+
+fn _goldilocks_value_in(witness_map: &WitnessMap, witness: &Witness) -> Result<F, String> {
+    Ok(field_element_to_goldilocks_field(&_field_element_in(
+        witness_map,
+        witness,
+    )?))
+}
+
+fn _field_element_in(witness_map: &WitnessMap, witness: &Witness) -> Result<FieldElement, String> {
+    witness_map
+        .get(witness)
+        .copied()
+        .ok_or(format!("witness {} has no value", witness.0))
+}
+
+fn _report_for(circuit: &Circuit, opcode_index: usize, opcode: &Opcode, reason: String) -> String {
+    let mut report = format!(
+        "Opcode {} is not satisfied by the witness ({}): {}",
+        opcode_index, reason, opcode
+    );
+    if let Some(assert_message) = _assert_message_for(circuit, opcode_index) {
+        report.push_str(&format!("\nAssertion message: {}", assert_message));
+    }
+    report
+}
+
+fn _assert_message_for(circuit: &Circuit, opcode_index: usize) -> Option<String> {
+    circuit
+        .assert_messages
+        .iter()
+        .find(|(location, _)| *location == OpcodeLocation::Acir(opcode_index))
+        .map(|(_, payload)| match payload {
+            AssertionPayload::StaticString(message) => message.clone(),
+            AssertionPayload::Dynamic(_, _) => "<dynamic message>".to_string(),
+        })
+}
@@ -0,0 +1,370 @@
+use super::*;
+use crate::circuit_translation::recursive_aggregation_translator::key_hash;
+use crate::circuit_translation::tests::factories::circuit_factory::{
+    circuit_with_opcodes, recursive_aggregation_circuit,
+};
+use crate::circuit_translation::tests::factories::utils::witness_map_from_values;
+use acir::circuit::opcodes::{BlackBoxFuncCall, BlockId, BlockType, FunctionInput};
+use acir::circuit::{AssertionPayload, OpcodeLocation};
+
+// fn main(x: pub Field, y: Field) {
+//     let z = x + y;
+//     assert(z == 5, "z must be 5");
+// }
+fn _sum_circuit() -> Circuit {
+    let mut circuit = circuit_with_opcodes(
+        vec![
+            Opcode::AssertZero(Expression {
+                mul_terms: vec![],
+                linear_combinations: vec![
+                    (FieldElement::one(), Witness(0)),
+                    (FieldElement::one(), Witness(1)),
+                    (-FieldElement::one(), Witness(2)),
+                ],
+                q_c: FieldElement::zero(),
+            }),
+            Opcode::AssertZero(Expression {
+                mul_terms: vec![],
+                linear_combinations: vec![(FieldElement::one(), Witness(2))],
+                q_c: -FieldElement::from(5u128),
+            }),
+        ],
+        vec![Witness(0)],
+        vec![],
+    );
+    circuit.assert_messages = vec![(
+        OpcodeLocation::Acir(1),
+        AssertionPayload::StaticString("z must be 5".to_string()),
+    )];
+    circuit
+}
+
+fn _check_circuit_witness(circuit: &Circuit, witness_map: &WitnessMap) -> Result<(), String> {
+    let program = Program {
+        functions: vec![circuit.clone()],
+        unconstrained_functions: vec![],
+    };
+    check_circuit_witness(&program, circuit, witness_map, &HashMap::new())
+}
+
+#[test]
+fn test_witness_checker_accepts_a_witness_that_satisfies_every_opcode() {
+    // Given
+    let circuit = _sum_circuit();
+    let witness_map = witness_map_from_values(vec![(0, 2), (1, 3), (2, 5)]);
+
+    // When
+    let result = _check_circuit_witness(&circuit, &witness_map);
+
+    //Then
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_witness_checker_reports_the_first_failing_opcode_and_its_assert_message() {
+    // Given
+    let circuit = _sum_circuit();
+    let witness_map = witness_map_from_values(vec![(0, 2), (1, 4), (2, 6)]);
+
+    // When
+    let report = _check_circuit_witness(&circuit, &witness_map).unwrap_err();
+
+    //Then
+    assert!(report.starts_with("Opcode 1 is not satisfied"));
+    assert!(report.ends_with("Assertion message: z must be 5"));
+}
+
+#[test]
+fn test_witness_checker_reports_witnesses_without_a_value() {
+    // Given
+    let circuit = _sum_circuit();
+    let witness_map = witness_map_from_values(vec![(0, 2), (1, 3)]);
+
+    // When
+    let report = _check_circuit_witness(&circuit, &witness_map).unwrap_err();
+
+    //Then
+    assert!(report.starts_with("Opcode 0 is not satisfied"));
+    assert!(report.contains("witness 2 has no value"));
+}
+
+#[test]
+fn test_witness_checker_reports_values_out_of_range() {
+    // Given
+    let input = FunctionInput {
+        witness: Witness(0),
+        num_bits: 8,
+    };
+    let circuit = circuit_with_opcodes(
+        vec![Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input })],
+        vec![Witness(0)],
+        vec![],
+    );
+    let witness_map = witness_map_from_values(vec![(0, 256)]);
+
+    // When
+    let report = _check_circuit_witness(&circuit, &witness_map).unwrap_err();
+
+    //Then
+    assert!(report.contains("the value does not fit in 8 bits"));
+}
+
+#[test]
+fn test_witness_checker_reports_a_hash_input_that_does_not_fit_in_a_byte() {
+    // Given
+    let input = FunctionInput {
+        witness: Witness(0),
+        num_bits: 8,
+    };
+    let output_witnesses: [Witness; 32] =
+        (1..33).map(Witness).collect::<Vec<_>>().try_into().unwrap();
+    let circuit = circuit_with_opcodes(
+        vec![Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SHA256 {
+            inputs: vec![input],
+            outputs: Box::new(output_witnesses),
+        })],
+        vec![Witness(0)],
+        vec![],
+    );
+    // 0x161 truncated to a byte is the 0x61 whose digest is given
+    let digest_of_truncated_input = Sha256::digest([0x61u8]);
+    let mut values = vec![(0, 0x161)];
+    for (position, byte) in digest_of_truncated_input.iter().enumerate() {
+        values.push((position as u32 + 1, *byte as u128));
+    }
+    let witness_map = witness_map_from_values(values);
+
+    // When
+    let report = _check_circuit_witness(&circuit, &witness_map).unwrap_err();
+
+    //Then
+    assert!(report.contains("an input of the hash does not fit in 8 bits"));
+}
+
+#[test]
+fn test_witness_checker_reports_a_wrong_bitwise_output() {
+    // Given
+    let lhs = FunctionInput {
+        witness: Witness(0),
+        num_bits: 8,
+    };
+    let rhs = FunctionInput {
+        witness: Witness(1),
+        num_bits: 8,
+    };
+    let circuit = circuit_with_opcodes(
+        vec![Opcode::BlackBoxFuncCall(BlackBoxFuncCall::XOR {
+            lhs,
+            rhs,
+            output: Witness(2),
+        })],
+        vec![Witness(0), Witness(1)],
+        vec![],
+    );
+    let witness_map = witness_map_from_values(vec![(0, 0b1100), (1, 0b1010), (2, 0b1110)]);
+
+    // When
+    let report = _check_circuit_witness(&circuit, &witness_map).unwrap_err();
+
+    //Then
+    assert!(report.contains("the output of the bitwise operation is wrong"));
+}
+
+#[test]
+fn test_witness_checker_reports_a_read_of_a_value_that_is_not_stored_in_memory() {
+    // fn main(x: [Field; 2], i: Field) -> pub Field {
+    //     x[i]
+    // }
+
+    // Given
+    let circuit = circuit_with_opcodes(
+        vec![
+            Opcode::MemoryInit {
+                block_id: BlockId(0),
+                init: vec![Witness(0), Witness(1)],
+                block_type: BlockType::Memory,
+            },
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp {
+                    operation: Expression::zero(),
+                    index: Expression::from(Witness(2)),
+                    value: Expression::from(Witness(3)),
+                },
+                predicate: None,
+            },
+        ],
+        vec![],
+        vec![],
+    );
+    let witness_map = witness_map_from_values(vec![(0, 10), (1, 20), (2, 1), (3, 10)]);
+
+    // When
+    let report = _check_circuit_witness(&circuit, &witness_map).unwrap_err();
+
+    //Then
+    assert!(report.contains("the value read at index 1 is wrong"));
+}
+
+#[test]
+#[should_panic(expected = "Function 0: Opcode 1 is not satisfied")]
+fn test_witness_checker_checks_every_item_of_the_witness_stack() {
+    // Given
+    let program = Program {
+        functions: vec![_sum_circuit()],
+        unconstrained_functions: vec![],
+    };
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(0, witness_map_from_values(vec![(0, 2), (1, 4), (2, 6)]));
+
+    // When
+    check_witness_stack(&program, &witness_stack);
+}
+
+#[test]
+fn test_witness_checker_reports_a_disabled_read_of_a_value_other_than_zero() {
+    // Given
+    let circuit = circuit_with_opcodes(
+        vec![
+            Opcode::MemoryInit {
+                block_id: BlockId(0),
+                init: vec![Witness(0)],
+                block_type: BlockType::Memory,
+            },
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp {
+                    operation: Expression::zero(),
+                    index: Expression::zero(),
+                    value: Expression::from(Witness(1)),
+                },
+                predicate: Some(Expression::from(Witness(2))),
+            },
+        ],
+        vec![],
+        vec![],
+    );
+    let witness_map = witness_map_from_values(vec![(0, 10), (1, 10), (2, 0)]);
+
+    // When
+    let report = _check_circuit_witness(&circuit, &witness_map).unwrap_err();
+
+    //Then
+    assert!(report.starts_with("Opcode 1 is not satisfied"));
+    assert!(report.contains("a read with a false predicate must read zero"));
+}
+
+// fn main(x: Field, enabled: bool) -> pub Field {
+//     if enabled { add_one(x) } else { 0 }
+// }
+// fn add_one(x: Field) -> Field {
+//     x + 1
+// }
+fn _program_calling_add_one(predicate: Option<Expression>) -> Program {
+    let main = circuit_with_opcodes(
+        vec![Opcode::Call {
+            id: 1,
+            inputs: vec![Witness(0)],
+            outputs: vec![Witness(1)],
+            predicate,
+        }],
+        vec![],
+        vec![Witness(1)],
+    );
+    let add_one = circuit_with_opcodes(
+        vec![Opcode::AssertZero(Expression {
+            mul_terms: vec![],
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(1)),
+                (-FieldElement::one(), Witness(0)),
+            ],
+            q_c: -FieldElement::one(),
+        })],
+        vec![],
+        vec![Witness(1)],
+    );
+    Program {
+        functions: vec![main, add_one],
+        unconstrained_functions: vec![],
+    }
+}
+
+#[test]
+fn test_witness_checker_accepts_call_outputs_returned_by_the_called_function() {
+    // Given
+    let program = _program_calling_add_one(None);
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(1, witness_map_from_values(vec![(0, 2), (1, 3)]));
+    witness_stack.push(0, witness_map_from_values(vec![(0, 2), (1, 3)]));
+
+    // When
+    check_witness_stack(&program, &witness_stack);
+}
+
+#[test]
+#[should_panic(
+    expected = "Function 0: Opcode 0 is not satisfied by the witness (output 0 differs from the value returned by the called function)"
+)]
+fn test_witness_checker_reports_a_call_output_not_returned_by_the_called_function() {
+    // Given
+    let program = _program_calling_add_one(None);
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(1, witness_map_from_values(vec![(0, 2), (1, 3)]));
+    witness_stack.push(0, witness_map_from_values(vec![(0, 2), (1, 4)]));
+
+    // When
+    check_witness_stack(&program, &witness_stack);
+}
+
+#[test]
+#[should_panic(
+    expected = "Function 0: Opcode 0 is not satisfied by the witness (input 0 differs from the one received by the called function)"
+)]
+fn test_witness_checker_reports_a_call_input_not_received_by_the_called_function() {
+    // Given
+    let program = _program_calling_add_one(None);
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(1, witness_map_from_values(vec![(0, 5), (1, 6)]));
+    witness_stack.push(0, witness_map_from_values(vec![(0, 2), (1, 6)]));
+
+    // When
+    check_witness_stack(&program, &witness_stack);
+}
+
+#[test]
+#[should_panic(
+    expected = "Function 0: Opcode 0 is not satisfied by the witness (a disabled call must output zero)"
+)]
+fn test_witness_checker_reports_a_disabled_call_with_outputs_other_than_zero() {
+    // Given
+    let program = _program_calling_add_one(Some(Expression::from(Witness(2))));
+    let mut witness_stack = WitnessStack::default();
+    witness_stack.push(0, witness_map_from_values(vec![(0, 2), (1, 3), (2, 0)]));
+
+    // When
+    check_witness_stack(&program, &witness_stack);
+}
+
+#[test]
+fn test_witness_checker_reports_a_key_hash_that_does_not_match_the_verifying_key() {
+    // Given
+    let circuit = recursive_aggregation_circuit(
+        vec![Witness(0), Witness(1)],
+        vec![Witness(2)],
+        vec![],
+        Witness(3),
+    );
+    let key_hash = key_hash(&vec![F::from_canonical_u64(7), F::from_canonical_u64(8)]);
+    let witness_map = witness_map_from_values(vec![
+        (0, 7),
+        (1, 8),
+        (2, 9),
+        (3, key_hash.to_canonical_u64() as u128 + 1),
+    ]);
+
+    // When
+    let report = _check_circuit_witness(&circuit, &witness_map).unwrap_err();
+
+    //Then
+    assert!(report.contains("the key hash does not match the verifying key"));
+}
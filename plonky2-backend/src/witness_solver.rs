@@ -0,0 +1,88 @@
+use acvm::acir::brillig::ForeignCallResult;
+use acvm::blackbox_solver::StubbedBlackBoxSolver;
+use acvm::pwg::{ACVMStatus, ForeignCallWaitInfo, ACVM};
+
+use super::*;
+
+// Solves the witness of every function of the program the same way nargo execute does,
+// pushing each function call into the stack once it finishes and main at the end
+pub fn solve_witness_stack(program: &Program, initial_witness: WitnessMap) -> WitnessStack {
+    let blackbox_solver = StubbedBlackBoxSolver;
+    let mut witness_stack = WitnessStack::default();
+    _solve_function_witness(
+        program,
+        &blackbox_solver,
+        0,
+        initial_witness,
+        &mut witness_stack,
+    );
+    witness_stack
+}
+
+fn _solve_function_witness(
+    program: &Program,
+    blackbox_solver: &StubbedBlackBoxSolver,
+    function_id: u32,
+    initial_witness: WitnessMap,
+    witness_stack: &mut WitnessStack,
+) -> WitnessMap {
+    let circuit = &program.functions[function_id as usize];
+    // Brillig calls are executed by the ACVM's own Brillig VM
+    let mut acvm = ACVM::new(
+        blackbox_solver,
+        &circuit.opcodes,
+        initial_witness,
+        &program.unconstrained_functions,
+        &circuit.assert_messages,
+    );
+    loop {
+        match acvm.solve() {
+            ACVMStatus::Solved => break,
+            ACVMStatus::InProgress => unreachable!("The ACVM only stops once solving is done"),
+            ACVMStatus::Failure(error) => {
+                panic!(
+                    "Failed to solve the witness of function {}: {}",
+                    function_id, error
+                )
+            }
+            ACVMStatus::RequiresForeignCall(foreign_call) => {
+                let foreign_call_result = _resolve_foreign_call(&foreign_call);
+                acvm.resolve_pending_foreign_call(foreign_call_result);
+            }
+            ACVMStatus::RequiresAcirCall(call_info) => {
+                let callee_witness = _solve_function_witness(
+                    program,
+                    blackbox_solver,
+                    call_info.id,
+                    call_info.initial_witness,
+                    witness_stack,
+                );
+                let callee = &program.functions[call_info.id as usize];
+                let return_values = callee
+                    .return_values
+                    .0
+                    .iter()
+                    .map(|witness| {
+                        *callee_witness
+                            .get(witness)
+                            .expect("Return values of a solved function must have a value")
+                    })
+                    .collect();
+                acvm.resolve_pending_acir_call(return_values);
+            }
+        }
+    }
+    let solved_witness = acvm.finalize();
+    witness_stack.push(function_id, solved_witness.clone());
+    solved_witness
+}
+
+// Printing has no effect on the witness, so it is answered with an empty result
+fn _resolve_foreign_call(
+    foreign_call: &ForeignCallWaitInfo<FieldElement>,
+) -> ForeignCallResult<FieldElement> {
+    match foreign_call.function.as_str() {
+        "print" => ForeignCallResult::default(),
+        function => panic!("Foreign call not supported yet: {}", function),
+    }
+}